@@ -0,0 +1,228 @@
+//! NDI Receiver Advertiser
+//!
+//! Registers receivers with a discovery server, so they can be listed and controlled by centrally
+//! managed routing systems. Advertised receivers can be listed with [crate::listener].
+//!
+//! <https://docs.ndi.video/all/developing-with-ndi/sdk/ndi-recv/receiver-discovery-and-monitoring>
+
+use std::{
+    error::Error,
+    ffi::{CString, NulError},
+    fmt::Debug,
+    ptr::NonNull,
+    sync::Arc,
+};
+
+use static_assertions::assert_impl_all;
+
+use crate::{bindings, receiver::NDIReceiver};
+
+/// Builder for [NDIRecvAdvertiser]
+#[non_exhaustive]
+#[derive(Debug, Clone, Default)]
+pub struct NDIRecvAdvertiserBuilder {
+    pub url_address: Option<CString>,
+}
+
+impl NDIRecvAdvertiserBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the URL of the discovery server, if not set the configured discovery server is used.
+    pub fn url_address(mut self, url_address: &str) -> Result<Self, NulError> {
+        self.url_address = Some(CString::new(url_address)?);
+        Ok(self)
+    }
+
+    pub fn build(self) -> Result<NDIRecvAdvertiser, NDIRecvAdvertiserBuilderError> {
+        let options = bindings::NDIlib_recv_advertiser_create_t {
+            p_url_address: self
+                .url_address
+                .as_ref()
+                .map(|url| url.as_ptr())
+                .unwrap_or(std::ptr::null()),
+        };
+
+        let handle = unsafe { bindings::NDIlib_recv_advertiser_create(&options) };
+
+        if let Some(handle) = NonNull::new(handle) {
+            Ok(NDIRecvAdvertiser {
+                handle: Arc::new(RawRecvAdvertiser { handle }),
+            })
+        } else {
+            Err(NDIRecvAdvertiserBuilderError::CreationFailed)
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NDIRecvAdvertiserBuilderError {
+    /// The SDK failed to create the advertiser, e.g. no discovery server is configured
+    CreationFailed,
+}
+
+impl std::fmt::Display for NDIRecvAdvertiserBuilderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CreationFailed => f.write_str("Creating NDI receiver advertiser failed"),
+        }
+    }
+}
+
+impl Error for NDIRecvAdvertiserBuilderError {}
+
+pub(crate) struct RawRecvAdvertiser {
+    handle: NonNull<bindings::NDIlib_recv_advertiser_instance_type>,
+}
+
+impl RawRecvAdvertiser {
+    pub(crate) fn raw_ptr(&self) -> bindings::NDIlib_recv_advertiser_instance_t {
+        self.handle.as_ptr()
+    }
+
+    pub(crate) unsafe fn del_receiver(&self, recv: bindings::NDIlib_recv_instance_t) -> bool {
+        unsafe { bindings::NDIlib_recv_advertiser_del_receiver(self.raw_ptr(), recv) }
+    }
+}
+
+impl Drop for RawRecvAdvertiser {
+    fn drop(&mut self) {
+        unsafe { bindings::NDIlib_recv_advertiser_destroy(self.raw_ptr()) };
+    }
+}
+
+impl Debug for RawRecvAdvertiser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RawRecvAdvertiser")
+            .field("raw_ptr", &self.raw_ptr())
+            .finish()
+    }
+}
+
+unsafe impl Send for RawRecvAdvertiser {}
+unsafe impl Sync for RawRecvAdvertiser {}
+
+/// Advertises receivers to a discovery server
+///
+/// Registered receivers keep a reference to the advertiser, the SDK instance is not destroyed
+/// until all of them are removed or destroyed. See [NDIRecvAdvertiser::add_receiver] for when a
+/// receiver is removed automatically.
+///
+/// C equivalent: `NDIlib_recv_advertiser_instance_t`
+#[derive(Debug)]
+pub struct NDIRecvAdvertiser {
+    handle: Arc<RawRecvAdvertiser>,
+}
+
+assert_impl_all!(NDIRecvAdvertiser: Send, Sync);
+
+impl NDIRecvAdvertiser {
+    /// Registers a receiver with the discovery server.
+    ///
+    /// The receiver is listed with the name set by [crate::receiver::NDIReceiverBuilder::name].
+    ///
+    /// - `input_group`: the group the receiver is listed in, the system default is used if not set
+    /// - `allow_controlling`: allows the routing system to change the source of the receiver
+    /// - `allow_monitoring`: allows the routing system to monitor the receiver
+    ///
+    /// The receiver is removed automatically once the underlying SDK instance is destroyed. Received
+    /// frames and [crate::framesync::NDIFrameSync] instances keep it alive after the
+    /// [NDIReceiver] itself is dropped, use [NDIRecvAdvertiser::remove_receiver] to deregister it
+    /// earlier.
+    pub fn add_receiver(
+        &self,
+        receiver: &NDIReceiver,
+        input_group: Option<&str>,
+        allow_controlling: bool,
+        allow_monitoring: bool,
+    ) -> Result<(), NDIRecvAdvertiserError> {
+        let input_group = input_group
+            .map(CString::new)
+            .transpose()
+            .map_err(|_| NDIRecvAdvertiserError::InvalidInputGroup)?;
+
+        let mut advertisers = receiver
+            .handle
+            .advertisers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+
+        if advertisers.iter().any(|a| Arc::ptr_eq(a, &self.handle)) {
+            Err(NDIRecvAdvertiserError::AlreadyAdded)?;
+        }
+
+        let added = unsafe {
+            bindings::NDIlib_recv_advertiser_add_receiver(
+                self.handle.raw_ptr(),
+                receiver.handle.raw_ptr(),
+                allow_controlling,
+                allow_monitoring,
+                input_group
+                    .as_ref()
+                    .map(|group| group.as_ptr())
+                    .unwrap_or(std::ptr::null()),
+            )
+        };
+
+        if added {
+            advertisers.push(self.handle.clone());
+            Ok(())
+        } else {
+            Err(NDIRecvAdvertiserError::AddFailed)
+        }
+    }
+
+    /// Removes a receiver from the discovery server before it is dropped.
+    pub fn remove_receiver(&self, receiver: &NDIReceiver) -> Result<(), NDIRecvAdvertiserError> {
+        let mut advertisers = receiver
+            .handle
+            .advertisers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+
+        let Some(index) = advertisers
+            .iter()
+            .position(|a| Arc::ptr_eq(a, &self.handle))
+        else {
+            return Err(NDIRecvAdvertiserError::NotAdded);
+        };
+        advertisers.swap_remove(index);
+
+        if unsafe { self.handle.del_receiver(receiver.handle.raw_ptr()) } {
+            Ok(())
+        } else {
+            Err(NDIRecvAdvertiserError::RemoveFailed)
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NDIRecvAdvertiserError {
+    /// The input group name contains a null byte
+    InvalidInputGroup,
+    /// The receiver is already registered with this advertiser
+    AlreadyAdded,
+    /// The receiver is not registered with this advertiser
+    NotAdded,
+    /// The SDK rejected the receiver
+    AddFailed,
+    /// The SDK failed to remove the receiver, it is no longer tracked anyway
+    RemoveFailed,
+}
+
+impl std::fmt::Display for NDIRecvAdvertiserError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidInputGroup => f.write_str("Input group name contains a null byte"),
+            Self::AlreadyAdded => f.write_str("Receiver is already advertised"),
+            Self::NotAdded => f.write_str("Receiver is not advertised"),
+            Self::AddFailed => f.write_str("Advertising the receiver failed"),
+            Self::RemoveFailed => f.write_str("Removing the receiver from the advertiser failed"),
+        }
+    }
+}
+
+impl Error for NDIRecvAdvertiserError {}
@@ -2,6 +2,9 @@
 
 use crate::{enums::NDIFieldedFrameMode, resolution::Resolution, subsampling::Subsampling};
 
+/// The maximum number of planes a supported video format consists of
+pub const MAX_PLANES: usize = 3;
+
 /// Contains information about the memory layout of video frame buffers
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
@@ -9,6 +12,7 @@ pub struct BufferInfo {
     /// The size of the buffer in bytes. Takes into account the field mode.
     pub size: usize,
     /// The stride/size of a single line in bytes.
+    /// For multi-planar formats this is the stride of the first plane (this is what the SDK expects in `line_stride_in_bytes`)
     pub line_stride: usize,
 
     pub resolution: Resolution,
@@ -16,4 +20,131 @@ pub struct BufferInfo {
     pub field_mode: NDIFieldedFrameMode,
     /// Information about chroma subsampling.
     pub subsampling: Subsampling,
+
+    planes: [PlaneInfo; MAX_PLANES],
+    num_planes: usize,
+}
+
+impl BufferInfo {
+    /// Builds the layout from a list of planes that are stored back to back in the buffer.
+    ///
+    /// The offsets of the planes are computed from their order.
+    pub(crate) fn from_planes(
+        resolution: Resolution,
+        field_mode: NDIFieldedFrameMode,
+        subsampling: Subsampling,
+        planes: &[PlaneInfo],
+    ) -> Self {
+        assert!(
+            !planes.is_empty() && planes.len() <= MAX_PLANES,
+            "[Invariant Error] Invalid number of planes: {}",
+            planes.len()
+        );
+
+        let mut info = BufferInfo {
+            size: 0,
+            line_stride: planes[0].line_stride,
+            resolution,
+            field_mode,
+            subsampling,
+            planes: [PlaneInfo::default(); MAX_PLANES],
+            num_planes: planes.len(),
+        };
+
+        for (i, plane) in planes.iter().enumerate() {
+            let plane = PlaneInfo {
+                offset: info.size,
+                ..*plane
+            };
+            info.size += plane.size();
+            info.planes[i] = plane;
+        }
+
+        info
+    }
+
+    /// The planes the buffer consists of, in memory order.
+    ///
+    /// Packed formats (like UYVY or RGBA) consist of a single plane.
+    pub fn planes(&self) -> &[PlaneInfo] {
+        &self.planes[..self.num_planes]
+    }
+
+    /// Gets the plane with the given index (in memory order)
+    pub fn plane(&self, index: usize) -> Option<&PlaneInfo> {
+        self.planes().get(index)
+    }
+
+    /// Gets the plane of the given kind
+    pub fn plane_of_kind(&self, kind: PlaneKind) -> Option<&PlaneInfo> {
+        self.planes().iter().find(|plane| plane.kind == kind)
+    }
+}
+
+/// Describes which components are stored in a plane
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum PlaneKind {
+    /// All components of a pixel are stored interleaved (like UYVY or RGBA)
+    #[default]
+    Packed,
+    /// Luma
+    Y,
+    /// Interleaved chroma, U (Cb) first
+    UV,
+    /// Chroma (Cb)
+    U,
+    /// Chroma (Cr)
+    V,
+    /// Alpha
+    A,
+}
+
+/// Describes the memory layout of a single plane inside a video frame buffer
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct PlaneInfo {
+    /// The components stored in this plane
+    pub kind: PlaneKind,
+    /// Offset of the first byte of the plane from the start of the buffer
+    pub offset: usize,
+    /// The stride/size of a single line of this plane in bytes.
+    pub line_stride: usize,
+    /// The number of lines of this plane. Takes into account the field mode.
+    pub height: usize,
+    /// Subsampling of the samples stored in this plane relative to the frame resolution.
+    /// Planes that store full resolution samples (luma/alpha/RGB) are not subsampled.
+    pub subsampling: Subsampling,
+    /// The size of a single sample in bytes (1 for 8bit formats, 2 for 16bit formats)
+    pub bytes_per_sample: usize,
+}
+
+impl PlaneInfo {
+    /// The offset is filled in by [BufferInfo::from_planes]
+    pub(crate) const fn new(
+        kind: PlaneKind,
+        line_stride: usize,
+        height: usize,
+        subsampling: Subsampling,
+        bytes_per_sample: usize,
+    ) -> Self {
+        PlaneInfo {
+            kind,
+            offset: 0,
+            line_stride,
+            height,
+            subsampling,
+            bytes_per_sample,
+        }
+    }
+
+    /// The size of the plane in bytes
+    pub const fn size(&self) -> usize {
+        self.line_stride * self.height
+    }
+
+    /// The byte range of the plane inside the frame buffer
+    pub const fn range(&self) -> std::ops::Range<usize> {
+        self.offset..self.offset + self.size()
+    }
 }
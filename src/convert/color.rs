@@ -0,0 +1,124 @@
+//! Color matrices and quantization ranges
+
+/// YCbCr <-> RGB conversion matrix
+///
+/// <https://docs.ndi.video/all/using-ndi/ndi-for-video/digital-video-basics#color-spaces>
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ColorMatrix {
+    /// ITU-R BT.601, used for SD video
+    BT601,
+    /// ITU-R BT.709, used for HD video
+    #[default]
+    BT709,
+    /// ITU-R BT.2020 (non-constant luminance), used for UHD video
+    BT2020,
+}
+
+impl ColorMatrix {
+    /// The luma coefficients `(Kr, Kb)`
+    pub const fn coefficients(self) -> (f32, f32) {
+        match self {
+            ColorMatrix::BT601 => (0.299, 0.114),
+            ColorMatrix::BT709 => (0.2126, 0.0722),
+            ColorMatrix::BT2020 => (0.2627, 0.0593),
+        }
+    }
+
+    /// Converts normalized RGB (`0..=1`) to normalized YCbCr (Y: `0..=1`, Cb/Cr: `-0.5..=0.5`)
+    pub(crate) fn rgb_to_ycbcr(self, [r, g, b]: [f32; 3]) -> [f32; 3] {
+        let (kr, kb) = self.coefficients();
+        let y = kr * r + (1. - kr - kb) * g + kb * b;
+        let cb = (b - y) / (2. * (1. - kb));
+        let cr = (r - y) / (2. * (1. - kr));
+        [y, cb, cr]
+    }
+
+    /// Converts normalized YCbCr (Y: `0..=1`, Cb/Cr: `-0.5..=0.5`) to normalized RGB (`0..=1`)
+    pub(crate) fn ycbcr_to_rgb(self, [y, cb, cr]: [f32; 3]) -> [f32; 3] {
+        let (kr, kb) = self.coefficients();
+        let r = y + 2. * (1. - kr) * cr;
+        let b = y + 2. * (1. - kb) * cb;
+        let g = (y - kr * r - kb * b) / (1. - kr - kb);
+        [r, g, b]
+    }
+}
+
+/// Quantization range of YCbCr samples. RGB samples always use the full range.
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ColorRange {
+    /// Y: 16-235, CbCr: 16-240 (for 8bit, scaled by 256 for 16bit)
+    ///
+    /// This is what NDI uses for all YCbCr formats
+    #[default]
+    Limited,
+    /// Y/CbCr: 0-255 (for 8bit, 0-65535 for 16bit)
+    Full,
+}
+
+/// Maps integer samples to normalized values and back
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Quantization {
+    offset: f32,
+    scale: f32,
+    max: f32,
+}
+
+impl Quantization {
+    pub(crate) const fn luma(range: ColorRange, bits: u32) -> Self {
+        let shift = (1 << (bits - 8)) as f32;
+        let max = ((1u32 << bits) - 1) as f32;
+        match range {
+            ColorRange::Limited => Quantization {
+                offset: 16. * shift,
+                scale: 219. * shift,
+                max,
+            },
+            ColorRange::Full => Quantization {
+                offset: 0.,
+                scale: max,
+                max,
+            },
+        }
+    }
+
+    pub(crate) const fn chroma(range: ColorRange, bits: u32) -> Self {
+        let shift = (1 << (bits - 8)) as f32;
+        let max = ((1u32 << bits) - 1) as f32;
+        match range {
+            ColorRange::Limited => Quantization {
+                offset: 128. * shift,
+                scale: 224. * shift,
+                max,
+            },
+            ColorRange::Full => Quantization {
+                offset: 128. * shift,
+                scale: max,
+                max,
+            },
+        }
+    }
+
+    /// RGB and alpha samples
+    pub(crate) const fn full(bits: u32) -> Self {
+        let max = ((1u32 << bits) - 1) as f32;
+        Quantization {
+            offset: 0.,
+            scale: max,
+            max,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn decode(self, sample: u16) -> f32 {
+        (sample as f32 - self.offset) / self.scale
+    }
+
+    #[inline]
+    pub(crate) fn encode(self, value: f32) -> u16 {
+        (value * self.scale + self.offset)
+            .round()
+            .clamp(0., self.max) as u16
+    }
+}
@@ -0,0 +1,409 @@
+//! Pure Rust pixel format conversion between all [FourCCVideo] formats
+//!
+//! This does not depend on the SDK runtime. Conversions between YCbCr formats only resample
+//! chroma/bit depth and don't touch the color matrix, conversions between YCbCr and RGB use the
+//! [ColorMatrix] and [ColorRange] given in the [ConversionOptions].
+//!
+//! Chroma is upsampled by sample repetition and downsampled by averaging.
+//!
+//! ```rust,no_run
+//! # use ndi_sdk_sys::{convert::ConversionOptions, four_cc::FourCCVideo, frame::video::VideoFrame};
+//! # let frame = VideoFrame::new();
+//! let bgra = frame
+//!     .convert_to(FourCCVideo::BGRA, &ConversionOptions::for_resolution(frame.resolution()))
+//!     .unwrap();
+//! ```
+
+mod color;
+mod pixel;
+
+use std::error::Error;
+
+pub use color::{ColorMatrix, ColorRange};
+
+use crate::{
+    four_cc::FourCCVideo,
+    frame::video::{VideoFrame, VideoFrameAccessError, VideoFrameAllocationError},
+    resolution::Resolution,
+};
+
+use pixel::ColorSpace;
+
+/// Options for pixel format conversions
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct ConversionOptions {
+    /// The matrix used between YCbCr and RGB, defaults to BT.709
+    pub matrix: ColorMatrix,
+    /// The quantization range of YCbCr samples, defaults to limited range
+    pub range: ColorRange,
+}
+
+impl ConversionOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Selects the matrix that is typically used for the given resolution:
+    /// BT.601 for SD, BT.709 for HD and BT.2020 for UHD
+    pub fn for_resolution(resolution: Resolution) -> Self {
+        let matrix = if resolution.y < 720 {
+            ColorMatrix::BT601
+        } else if resolution.y <= 1080 {
+            ColorMatrix::BT709
+        } else {
+            ColorMatrix::BT2020
+        };
+        Self::default().matrix(matrix)
+    }
+
+    pub fn matrix(mut self, matrix: ColorMatrix) -> Self {
+        self.matrix = matrix;
+        self
+    }
+
+    pub fn range(mut self, range: ColorRange) -> Self {
+        self.range = range;
+        self
+    }
+}
+
+/// Converts a video frame into a newly allocated frame with the given format.
+///
+/// Resolution, field mode, frame rate and timing information are carried over, metadata is not.
+pub fn convert_video_frame(
+    src: &VideoFrame,
+    format: FourCCVideo,
+    options: &ConversionOptions,
+) -> Result<VideoFrame, ConversionError> {
+    let src_planes = src.planes().map_err(ConversionError::SourceNotReadable)?;
+    let src_format = src
+        .four_cc()
+        .expect("[Invariant Error] Frame with unknown FourCC has a buffer layout");
+
+    let mut dst = VideoFrame::new();
+    dst.raw.xres = src.raw.xres;
+    dst.raw.yres = src.raw.yres;
+    dst.raw.frame_format_type = src.raw.frame_format_type;
+    dst.raw.frame_rate_N = src.raw.frame_rate_N;
+    dst.raw.frame_rate_D = src.raw.frame_rate_D;
+    dst.raw.picture_aspect_ratio = src.raw.picture_aspect_ratio;
+    dst.raw.timecode = src.raw.timecode;
+    dst.raw.timestamp = src.raw.timestamp;
+    dst.set_four_cc(format)
+        .expect("[Invariant Error] Fresh frame is already allocated");
+    dst.try_alloc().map_err(ConversionError::AllocationFailed)?;
+
+    let dst_planes = dst
+        .planes_mut()
+        .expect("[Invariant Error] Freshly allocated frame is not writable");
+
+    if src_format == format {
+        for (src_plane, mut dst_plane) in src_planes.iter().zip(dst_planes) {
+            dst_plane
+                .as_bytes_mut()
+                .copy_from_slice(src_plane.as_bytes());
+        }
+    } else {
+        let mut image = pixel::decode(src_format, &src_planes, options.range);
+        image.convert_space(ColorSpace::of(format), options.matrix);
+        pixel::encode(format, &image, dst_planes, options.range);
+    }
+
+    Ok(dst)
+}
+
+impl VideoFrame {
+    /// Converts the frame into a newly allocated frame with the given format, see [convert_video_frame]
+    pub fn convert_to(
+        &self,
+        format: FourCCVideo,
+        options: &ConversionOptions,
+    ) -> Result<VideoFrame, ConversionError> {
+        convert_video_frame(self, format, options)
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversionError {
+    /// The source frame could not be read
+    SourceNotReadable(VideoFrameAccessError),
+    /// The destination frame could not be allocated
+    AllocationFailed(VideoFrameAllocationError),
+}
+
+impl std::fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SourceNotReadable(err) => write!(f, "Source frame is not readable: {err}"),
+            Self::AllocationFailed(err) => {
+                write!(f, "Allocating the converted frame failed: {err}")
+            }
+        }
+    }
+}
+
+impl Error for ConversionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::SourceNotReadable(err) => Some(err),
+            Self::AllocationFailed(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::Rational32;
+
+    use super::*;
+    use crate::enums::NDIFieldedFrameMode;
+
+    const ALL_FORMATS: [FourCCVideo; 11] = [
+        FourCCVideo::UYVY,
+        FourCCVideo::UYVA,
+        FourCCVideo::P216,
+        FourCCVideo::PA16,
+        FourCCVideo::NV12,
+        FourCCVideo::I420,
+        FourCCVideo::YV12,
+        FourCCVideo::RGBA,
+        FourCCVideo::RGBX,
+        FourCCVideo::BGRA,
+        FourCCVideo::BGRX,
+    ];
+
+    fn has_alpha(format: FourCCVideo) -> bool {
+        use FourCCVideo::*;
+        matches!(format, UYVA | PA16 | RGBA | BGRA)
+    }
+
+    fn frame(format: FourCCVideo, fill: impl Fn(usize) -> u8) -> VideoFrame {
+        let mut frame = VideoFrame::new();
+        frame.set_resolution(Resolution::new(8, 4)).unwrap();
+        frame.set_four_cc(format).unwrap();
+        frame.alloc();
+        for (i, byte) in frame.video_data_mut().unwrap().0.iter_mut().enumerate() {
+            *byte = fill(i);
+        }
+        frame
+    }
+
+    fn rgba(color: [u8; 4]) -> VideoFrame {
+        frame(FourCCVideo::RGBA, |i| color[i % 4])
+    }
+
+    fn data(frame: &VideoFrame) -> &[u8] {
+        frame.video_data().unwrap().0
+    }
+
+    fn uyvy_of(color: [u8; 4], matrix: ColorMatrix, range: ColorRange) -> [u8; 4] {
+        let options = ConversionOptions::new().matrix(matrix).range(range);
+        let uyvy = rgba(color).convert_to(FourCCVideo::UYVY, &options).unwrap();
+        data(&uyvy)[0..4].try_into().unwrap()
+    }
+
+    #[test]
+    fn test_reference_values() {
+        use ColorMatrix::*;
+        use ColorRange::*;
+
+        let red = [255, 0, 0, 255];
+        assert_eq!(uyvy_of(red, BT601, Limited), [90, 81, 240, 81]);
+        assert_eq!(uyvy_of(red, BT709, Limited), [102, 63, 240, 63]);
+        assert_eq!(uyvy_of(red, BT2020, Limited), [97, 74, 240, 74]);
+
+        for matrix in [BT601, BT709, BT2020] {
+            assert_eq!(uyvy_of([0, 0, 0, 255], matrix, Limited), [128, 16, 128, 16]);
+            assert_eq!(uyvy_of([255; 4], matrix, Limited), [128, 235, 128, 235]);
+            assert_eq!(uyvy_of([0, 0, 0, 255], matrix, Full), [128, 0, 128, 0]);
+            assert_eq!(uyvy_of([255; 4], matrix, Full), [128, 255, 128, 255]);
+        }
+    }
+
+    #[test]
+    fn test_rgb_round_trip() {
+        let colors = [
+            [255, 0, 0, 255],
+            [0, 255, 0, 255],
+            [0, 0, 255, 255],
+            [12, 200, 99, 255],
+            [128, 128, 128, 255],
+        ];
+        for matrix in [ColorMatrix::BT601, ColorMatrix::BT709, ColorMatrix::BT2020] {
+            for range in [ColorRange::Limited, ColorRange::Full] {
+                let options = ConversionOptions::new().matrix(matrix).range(range);
+                for color in colors {
+                    for format in [FourCCVideo::UYVY, FourCCVideo::PA16, FourCCVideo::NV12] {
+                        let yuv = rgba(color).convert_to(format, &options).unwrap();
+                        let back = yuv.convert_to(FourCCVideo::RGBA, &options).unwrap();
+                        for (got, expected) in data(&back).iter().zip(color.iter().cycle()) {
+                            assert!(
+                                got.abs_diff(*expected) <= 2,
+                                "{color:?} via {format:?} ({matrix:?}, {range:?}) became {:?}",
+                                &data(&back)[0..4]
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_4_2_2_lossless() {
+        let src = frame(FourCCVideo::UYVY, |i| (i * 37 % 256) as u8);
+        let options = ConversionOptions::new();
+
+        let mut frame = src.convert_to(FourCCVideo::UYVY, &options).unwrap();
+        for format in [FourCCVideo::UYVA, FourCCVideo::P216, FourCCVideo::PA16] {
+            frame = frame.convert_to(format, &options).unwrap();
+        }
+        let back = frame.convert_to(FourCCVideo::UYVY, &options).unwrap();
+        assert_eq!(data(&back), data(&src));
+
+        let p216 = src.convert_to(FourCCVideo::P216, &options).unwrap();
+        let planes = p216.planes().unwrap();
+        let luma = planes.y().unwrap().samples16().unwrap();
+        assert_eq!(luma.get(0, 0), Some((data(&src)[1] as u16) << 8));
+    }
+
+    #[test]
+    fn test_4_2_0_lossless() {
+        // chroma is identical for every line pair, so nothing is lost by vertical subsampling
+        let src = frame(FourCCVideo::UYVY, |i| {
+            let (line, x) = (i / 16, i % 16);
+            if x % 2 == 0 {
+                ((line / 2) * 40 + x * 7) as u8
+            } else {
+                (i * 13 % 256) as u8
+            }
+        });
+        let options = ConversionOptions::new();
+
+        let mut frame = src.convert_to(FourCCVideo::NV12, &options).unwrap();
+        for format in [FourCCVideo::I420, FourCCVideo::YV12, FourCCVideo::NV12] {
+            frame = frame.convert_to(format, &options).unwrap();
+        }
+        let back = frame.convert_to(FourCCVideo::UYVY, &options).unwrap();
+        assert_eq!(data(&back), data(&src));
+
+        let i420 = src.convert_to(FourCCVideo::I420, &options).unwrap();
+        let yv12 = src.convert_to(FourCCVideo::YV12, &options).unwrap();
+        let (i420, yv12) = (i420.planes().unwrap(), yv12.planes().unwrap());
+        assert_eq!(i420.u().unwrap().as_bytes(), yv12.u().unwrap().as_bytes());
+        assert_eq!(i420.u().unwrap().row(0).unwrap()[0], data(&src)[0]);
+        assert_eq!(
+            i420.v().unwrap().row(1).unwrap()[3],
+            data(&src)[2 * 16 + 14]
+        );
+    }
+
+    #[test]
+    fn test_rgb_swizzle() {
+        let options = ConversionOptions::new();
+        let src = rgba([1, 2, 3, 4]);
+
+        let bgra = src.convert_to(FourCCVideo::BGRA, &options).unwrap();
+        assert_eq!(&data(&bgra)[0..4], &[3, 2, 1, 4]);
+
+        let rgbx = bgra.convert_to(FourCCVideo::RGBX, &options).unwrap();
+        assert_eq!(&data(&rgbx)[0..4], &[1, 2, 3, 255]);
+
+        let bgrx = src.convert_to(FourCCVideo::BGRX, &options).unwrap();
+        assert_eq!(&data(&bgrx)[0..4], &[3, 2, 1, 255]);
+    }
+
+    #[test]
+    fn test_alpha() {
+        let options = ConversionOptions::new();
+        let src = rgba([10, 20, 30, 128]);
+
+        let pa16 = src.convert_to(FourCCVideo::PA16, &options).unwrap();
+        let planes = pa16.planes().unwrap();
+        let alpha = planes.a().unwrap().samples16().unwrap();
+        assert!(alpha.rows().all(|row| row.iter().all(|a| a == 128 * 257)));
+
+        let uyva = pa16.convert_to(FourCCVideo::UYVA, &options).unwrap();
+        let planes = uyva.planes().unwrap();
+        assert!(planes.a().unwrap().as_bytes().iter().all(|&a| a == 128));
+
+        let back = uyva.convert_to(FourCCVideo::BGRA, &options).unwrap();
+        assert_eq!(data(&back)[3], 128);
+
+        let opaque = src.convert_to(FourCCVideo::UYVY, &options).unwrap();
+        let opaque = opaque.convert_to(FourCCVideo::UYVA, &options).unwrap();
+        let planes = opaque.planes().unwrap();
+        assert!(planes.a().unwrap().as_bytes().iter().all(|&a| a == 255));
+    }
+
+    #[test]
+    fn test_all_pairs() {
+        let options = ConversionOptions::new();
+        for src_format in ALL_FORMATS {
+            // mid gray, in YCbCr formats this is a valid limited range value for all components
+            let fill = |i: usize| match src_format {
+                FourCCVideo::P216 | FourCCVideo::PA16 if i.is_multiple_of(2) => 0,
+                FourCCVideo::RGBX | FourCCVideo::BGRX if i % 4 == 3 => 255,
+                _ => 128,
+            };
+            let src = frame(src_format, fill);
+            let bytes = src.buffer_info().unwrap().planes()[0].bytes_per_sample;
+            for dst_format in ALL_FORMATS {
+                let dst = src.convert_to(dst_format, &options).unwrap();
+                assert_eq!(dst.four_cc(), Some(dst_format));
+                assert_eq!(dst.resolution(), src.resolution());
+
+                let back = dst.convert_to(src_format, &options).unwrap();
+                if !has_alpha(src_format) || has_alpha(dst_format) {
+                    let pairs = data(&back).iter().zip(data(&src)).enumerate();
+                    // only the most significant byte of 16bit samples survives an 8bit format
+                    for (i, (got, expected)) in pairs.skip(bytes - 1).step_by(bytes) {
+                        assert!(
+                            got.abs_diff(*expected) <= 1,
+                            "{src_format:?} -> {dst_format:?} -> {src_format:?} differs at {i}: {got} != {expected}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_properties_are_copied() {
+        let mut src = VideoFrame::new();
+        src.set_resolution(Resolution::new(8, 4)).unwrap();
+        src.set_four_cc(FourCCVideo::UYVY).unwrap();
+        src.set_frame_format(NDIFieldedFrameMode::Field1).unwrap();
+        src.set_frame_rate(Rational32::new(50, 1));
+        src.alloc();
+
+        let dst = src
+            .convert_to(FourCCVideo::I420, &ConversionOptions::new())
+            .unwrap();
+        assert_eq!(dst.field_mode(), NDIFieldedFrameMode::Field1);
+        assert_eq!(dst.frame_rate(), Rational32::new(50, 1));
+        assert_eq!(dst.buffer_info().unwrap().planes()[0].height, 2);
+        assert_eq!(dst.send_time(), src.send_time());
+    }
+
+    #[test]
+    fn test_unallocated_source() {
+        let err = VideoFrame::new()
+            .convert_to(FourCCVideo::RGBA, &ConversionOptions::new())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ConversionError::SourceNotReadable(VideoFrameAccessError::NotAllocated)
+        );
+    }
+
+    #[test]
+    fn test_matrix_for_resolution() {
+        let matrix = |x, y| ConversionOptions::for_resolution(Resolution::new(x, y)).matrix;
+        assert_eq!(matrix(720, 576), ColorMatrix::BT601);
+        assert_eq!(matrix(1920, 1080), ColorMatrix::BT709);
+        assert_eq!(matrix(3840, 2160), ColorMatrix::BT2020);
+    }
+}
@@ -0,0 +1,355 @@
+//! Decoding/encoding of the individual FourCC formats into/from an intermediate 4:4:4 image
+
+use crate::{
+    buffer_info::PlaneKind,
+    four_cc::FourCCVideo,
+    frame::plane::{PlaneMut, PlaneRef, VideoPlanes},
+};
+
+use super::color::{ColorMatrix, ColorRange, Quantization};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ColorSpace {
+    /// Components are `[Y, Cb, Cr, A]`
+    YCbCr,
+    /// Components are `[R, G, B, A]`
+    Rgb,
+}
+
+impl ColorSpace {
+    pub(crate) fn of(format: FourCCVideo) -> Self {
+        use FourCCVideo::*;
+        match format {
+            RGBA | RGBX | BGRA | BGRX => ColorSpace::Rgb,
+            UYVY | UYVA | P216 | PA16 | NV12 | I420 | YV12 => ColorSpace::YCbCr,
+        }
+    }
+}
+
+/// Full resolution image with normalized components
+///
+/// Y, R, G, B and A are in the range `0..=1`, Cb and Cr in the range `-0.5..=0.5`
+pub(crate) struct Image {
+    width: usize,
+    height: usize,
+    space: ColorSpace,
+    pixels: Vec<[f32; 4]>,
+}
+
+impl Image {
+    fn new(width: usize, height: usize, space: ColorSpace) -> Self {
+        Image {
+            width,
+            height,
+            space,
+            pixels: vec![[0., 0., 0., 1.]; width * height],
+        }
+    }
+
+    #[inline]
+    fn get(&self, x: usize, y: usize) -> [f32; 4] {
+        self.pixels[y * self.width + x]
+    }
+
+    #[inline]
+    fn set(&mut self, x: usize, y: usize, pixel: [f32; 4]) {
+        self.pixels[y * self.width + x] = pixel;
+    }
+
+    #[inline]
+    fn set_alpha(&mut self, x: usize, y: usize, alpha: f32) {
+        self.pixels[y * self.width + x][3] = alpha;
+    }
+
+    /// Averages Cb and Cr of a block of pixels, the block is clipped at the image border
+    fn chroma(&self, x0: usize, y0: usize, block_x: usize, block_y: usize) -> (f32, f32) {
+        let (mut cb, mut cr, mut n) = (0., 0., 0.);
+        for y in y0..(y0 + block_y).min(self.height) {
+            for x in x0..(x0 + block_x).min(self.width) {
+                let [_, u, v, _] = self.get(x, y);
+                cb += u;
+                cr += v;
+                n += 1.;
+            }
+        }
+        (cb / n, cr / n)
+    }
+
+    pub(crate) fn convert_space(&mut self, space: ColorSpace, matrix: ColorMatrix) {
+        match (self.space, space) {
+            (ColorSpace::YCbCr, ColorSpace::Rgb) => {
+                for px in &mut self.pixels {
+                    let [r, g, b] = matrix.ycbcr_to_rgb([px[0], px[1], px[2]]);
+                    *px = [r, g, b, px[3]];
+                }
+            }
+            (ColorSpace::Rgb, ColorSpace::YCbCr) => {
+                for px in &mut self.pixels {
+                    let [y, cb, cr] = matrix.rgb_to_ycbcr([px[0], px[1], px[2]]);
+                    *px = [y, cb, cr, px[3]];
+                }
+            }
+            _ => {}
+        }
+        self.space = space;
+    }
+}
+
+/// Channel order of packed RGB formats: indices of R, G, B, A in memory and whether alpha is used
+fn rgb_layout(format: FourCCVideo) -> ([usize; 4], bool) {
+    match format {
+        FourCCVideo::RGBA => ([0, 1, 2, 3], true),
+        FourCCVideo::RGBX => ([0, 1, 2, 3], false),
+        FourCCVideo::BGRA => ([2, 1, 0, 3], true),
+        FourCCVideo::BGRX => ([2, 1, 0, 3], false),
+        _ => unreachable!("{format:?} is not an RGB format"),
+    }
+}
+
+fn plane<'p, 'a>(planes: &'p VideoPlanes<PlaneRef<'a>>, kind: PlaneKind) -> &'p PlaneRef<'a> {
+    planes
+        .get(kind)
+        .expect("[Invariant Error] Frame layout does not contain the expected plane")
+}
+
+fn take_plane<'a>(planes: &mut VideoPlanes<PlaneMut<'a>>, kind: PlaneKind) -> PlaneMut<'a> {
+    planes
+        .take(kind)
+        .expect("[Invariant Error] Frame layout does not contain the expected plane")
+}
+
+fn decode_alpha(image: &mut Image, alpha: &PlaneRef<'_>) {
+    if let Some(alpha) = alpha.samples16() {
+        let q = Quantization::full(16);
+        for (y, row) in alpha.rows().enumerate() {
+            for (x, a) in row.iter().enumerate() {
+                image.set_alpha(x, y, q.decode(a));
+            }
+        }
+    } else {
+        let q = Quantization::full(8);
+        for (y, row) in alpha.rows().enumerate() {
+            for (x, &a) in row.iter().enumerate() {
+                image.set_alpha(x, y, q.decode(a as u16));
+            }
+        }
+    }
+}
+
+fn encode_alpha(image: &Image, mut alpha: PlaneMut<'_>) {
+    if let Some(mut alpha) = alpha.samples16_mut() {
+        let q = Quantization::full(16);
+        for (y, mut row) in alpha.rows_mut().enumerate() {
+            row.copy_from((0..image.width).map(|x| q.encode(image.get(x, y)[3])));
+        }
+    } else {
+        let q = Quantization::full(8);
+        for (y, row) in alpha.rows_mut().enumerate() {
+            for (x, a) in row.iter_mut().enumerate() {
+                *a = q.encode(image.get(x, y)[3]) as u8;
+            }
+        }
+    }
+}
+
+/// Decodes the frame buffer into a full resolution image
+pub(crate) fn decode(
+    format: FourCCVideo,
+    planes: &VideoPlanes<PlaneRef<'_>>,
+    range: ColorRange,
+) -> Image {
+    use FourCCVideo::*;
+
+    let info = planes.buffer_info();
+    let width = info.resolution.x;
+    let height = info.planes()[0].height;
+    let mut image = Image::new(width, height, ColorSpace::of(format));
+
+    let bits = if matches!(format, P216 | PA16) { 16 } else { 8 };
+    let luma = Quantization::luma(range, bits);
+    let chroma = Quantization::chroma(range, bits);
+
+    match format {
+        UYVY | UYVA => {
+            for (y, row) in plane(planes, PlaneKind::Packed).rows().enumerate() {
+                for (pair, px) in row.chunks_exact(4).enumerate() {
+                    let cb = chroma.decode(px[0] as u16);
+                    let cr = chroma.decode(px[2] as u16);
+                    image.set(pair * 2, y, [luma.decode(px[1] as u16), cb, cr, 1.]);
+                    image.set(pair * 2 + 1, y, [luma.decode(px[3] as u16), cb, cr, 1.]);
+                }
+            }
+        }
+        P216 | PA16 => {
+            let luma_plane = plane(planes, PlaneKind::Y).samples16().unwrap();
+            let chroma_plane = plane(planes, PlaneKind::UV).samples16().unwrap();
+            for (y, (luma_row, chroma_row)) in
+                luma_plane.rows().zip(chroma_plane.rows()).enumerate()
+            {
+                for (x, l) in luma_row.iter().enumerate() {
+                    let pair = x / 2 * 2;
+                    let cb = chroma.decode(chroma_row.get(pair).unwrap());
+                    let cr = chroma.decode(chroma_row.get(pair + 1).unwrap());
+                    image.set(x, y, [luma.decode(l), cb, cr, 1.]);
+                }
+            }
+        }
+        NV12 => {
+            let chroma_plane = plane(planes, PlaneKind::UV);
+            for (y, luma_row) in plane(planes, PlaneKind::Y).rows().enumerate() {
+                let chroma_row = chroma_plane.row(y / 2).unwrap();
+                for (x, &l) in luma_row.iter().enumerate() {
+                    let pair = x / 2 * 2;
+                    let cb = chroma.decode(chroma_row[pair] as u16);
+                    let cr = chroma.decode(chroma_row[pair + 1] as u16);
+                    image.set(x, y, [luma.decode(l as u16), cb, cr, 1.]);
+                }
+            }
+        }
+        I420 | YV12 => {
+            let u_plane = plane(planes, PlaneKind::U);
+            let v_plane = plane(planes, PlaneKind::V);
+            for (y, luma_row) in plane(planes, PlaneKind::Y).rows().enumerate() {
+                let u_row = u_plane.row(y / 2).unwrap();
+                let v_row = v_plane.row(y / 2).unwrap();
+                for (x, &l) in luma_row.iter().enumerate() {
+                    let cb = chroma.decode(u_row[x / 2] as u16);
+                    let cr = chroma.decode(v_row[x / 2] as u16);
+                    image.set(x, y, [luma.decode(l as u16), cb, cr, 1.]);
+                }
+            }
+        }
+        RGBA | RGBX | BGRA | BGRX => {
+            let ([r, g, b, a], has_alpha) = rgb_layout(format);
+            let q = Quantization::full(8);
+            for (y, row) in plane(planes, PlaneKind::Packed).rows().enumerate() {
+                for (x, px) in row.chunks_exact(4).enumerate() {
+                    let alpha = if has_alpha {
+                        q.decode(px[a] as u16)
+                    } else {
+                        1.
+                    };
+                    image.set(
+                        x,
+                        y,
+                        [
+                            q.decode(px[r] as u16),
+                            q.decode(px[g] as u16),
+                            q.decode(px[b] as u16),
+                            alpha,
+                        ],
+                    );
+                }
+            }
+        }
+    }
+
+    if let Some(alpha) = planes.a() {
+        decode_alpha(&mut image, alpha);
+    }
+
+    image
+}
+
+/// Encodes a full resolution image into the frame buffer, chroma is averaged for subsampled formats
+///
+/// The image has to be in the color space of the format
+pub(crate) fn encode(
+    format: FourCCVideo,
+    image: &Image,
+    mut planes: VideoPlanes<PlaneMut<'_>>,
+    range: ColorRange,
+) {
+    use FourCCVideo::*;
+
+    assert_eq!(
+        image.space,
+        ColorSpace::of(format),
+        "[Invariant Error] Image has the wrong color space"
+    );
+
+    let bits = if matches!(format, P216 | PA16) { 16 } else { 8 };
+    let luma = Quantization::luma(range, bits);
+    let chroma = Quantization::chroma(range, bits);
+
+    match format {
+        UYVY | UYVA => {
+            let mut packed = take_plane(&mut planes, PlaneKind::Packed);
+            for (y, row) in packed.rows_mut().enumerate() {
+                for (pair, px) in row.chunks_exact_mut(4).enumerate() {
+                    let x = pair * 2;
+                    let (cb, cr) = image.chroma(x, y, 2, 1);
+                    px[0] = chroma.encode(cb) as u8;
+                    px[1] = luma.encode(image.get(x, y)[0]) as u8;
+                    px[2] = chroma.encode(cr) as u8;
+                    px[3] = luma.encode(image.get(x + 1, y)[0]) as u8;
+                }
+            }
+        }
+        P216 | PA16 => {
+            let mut luma_plane = take_plane(&mut planes, PlaneKind::Y);
+            for (y, mut row) in luma_plane.samples16_mut().unwrap().rows_mut().enumerate() {
+                row.copy_from((0..image.width).map(|x| luma.encode(image.get(x, y)[0])));
+            }
+
+            let mut chroma_plane = take_plane(&mut planes, PlaneKind::UV);
+            for (y, mut row) in chroma_plane.samples16_mut().unwrap().rows_mut().enumerate() {
+                row.copy_from((0..image.width / 2).flat_map(|pair| {
+                    let (cb, cr) = image.chroma(pair * 2, y, 2, 1);
+                    [chroma.encode(cb), chroma.encode(cr)]
+                }));
+            }
+        }
+        NV12 | I420 | YV12 => {
+            let mut luma_plane = take_plane(&mut planes, PlaneKind::Y);
+            for (y, row) in luma_plane.rows_mut().enumerate() {
+                for (x, l) in row.iter_mut().enumerate() {
+                    *l = luma.encode(image.get(x, y)[0]) as u8;
+                }
+            }
+
+            if format == NV12 {
+                let mut chroma_plane = take_plane(&mut planes, PlaneKind::UV);
+                for (cy, row) in chroma_plane.rows_mut().enumerate() {
+                    for (pair, px) in row.chunks_exact_mut(2).enumerate() {
+                        let (cb, cr) = image.chroma(pair * 2, cy * 2, 2, 2);
+                        px[0] = chroma.encode(cb) as u8;
+                        px[1] = chroma.encode(cr) as u8;
+                    }
+                }
+            } else {
+                let mut u_plane = take_plane(&mut planes, PlaneKind::U);
+                let mut v_plane = take_plane(&mut planes, PlaneKind::V);
+                for (cy, (u_row, v_row)) in u_plane.rows_mut().zip(v_plane.rows_mut()).enumerate() {
+                    for (cx, (u, v)) in u_row.iter_mut().zip(v_row.iter_mut()).enumerate() {
+                        let (cb, cr) = image.chroma(cx * 2, cy * 2, 2, 2);
+                        *u = chroma.encode(cb) as u8;
+                        *v = chroma.encode(cr) as u8;
+                    }
+                }
+            }
+        }
+        RGBA | RGBX | BGRA | BGRX => {
+            let ([r, g, b, a], has_alpha) = rgb_layout(format);
+            let q = Quantization::full(8);
+            let mut packed = take_plane(&mut planes, PlaneKind::Packed);
+            for (y, row) in packed.rows_mut().enumerate() {
+                for (x, px) in row.chunks_exact_mut(4).enumerate() {
+                    let [red, green, blue, alpha] = image.get(x, y);
+                    px[r] = q.encode(red) as u8;
+                    px[g] = q.encode(green) as u8;
+                    px[b] = q.encode(blue) as u8;
+                    px[a] = if has_alpha {
+                        q.encode(alpha) as u8
+                    } else {
+                        0xff
+                    };
+                }
+            }
+        }
+    }
+
+    if let Some(alpha) = planes.take(PlaneKind::A) {
+        encode_alpha(image, alpha);
+    }
+}
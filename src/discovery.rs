@@ -0,0 +1,263 @@
+//! Background source discovery
+//!
+//! [NDIDiscoveryService] owns a [NDISourceFinder] on a dedicated thread, so the current source list
+//! can be shared between threads without the `&mut` borrow required by [NDISourceFinder::get_source_iter].
+
+use std::{
+    fmt::Debug,
+    sync::{
+        Arc, Mutex, RwLock,
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use static_assertions::assert_impl_all;
+
+use crate::{
+    find::{NDISourceEvent, NDISourceFinder, NDISourceWatcher},
+    source::NDISource,
+};
+
+/// State shared between the discovery thread and all handles
+#[derive(Debug, Default)]
+struct Shared {
+    sources: RwLock<Arc<Vec<NDISource>>>,
+    subscribers: Mutex<Subscribers>,
+}
+
+#[derive(Debug, Default)]
+struct Subscribers {
+    senders: Vec<mpsc::Sender<NDISourceEvent>>,
+    stopped: bool,
+}
+
+impl Shared {
+    fn snapshot(&self) -> Arc<Vec<NDISource>> {
+        self.sources
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Replaces the snapshot and notifies all subscribers, disconnected subscribers are removed
+    fn publish(&self, sources: Vec<NDISource>, events: Vec<NDISourceEvent>) {
+        // Holding the subscriber lock while updating the snapshot ensures that a new subscriber
+        // either sees the old snapshot and receives the events or sees the new snapshot
+        let mut subscribers = self.subscribers.lock().unwrap_or_else(|e| e.into_inner());
+
+        *self.sources.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(sources);
+
+        subscribers
+            .senders
+            .retain(|tx| events.iter().all(|event| tx.send(event.clone()).is_ok()));
+    }
+
+    fn subscribe(&self) -> mpsc::Receiver<NDISourceEvent> {
+        let mut subscribers = self.subscribers.lock().unwrap_or_else(|e| e.into_inner());
+
+        let (tx, rx) = mpsc::channel();
+        for source in self.snapshot().iter() {
+            tx.send(NDISourceEvent::Added(source.clone()))
+                .expect("[Invariant Error] Receiver dropped during subscribe");
+        }
+
+        // Once stopped the sender is dropped, so the receiver disconnects after the initial events
+        if !subscribers.stopped {
+            subscribers.senders.push(tx);
+        }
+
+        rx
+    }
+
+    fn stop(&self) {
+        let mut subscribers = self.subscribers.lock().unwrap_or_else(|e| e.into_inner());
+        subscribers.stopped = true;
+        subscribers.senders.clear();
+    }
+}
+
+/// A cheap to clone handle to a [NDIDiscoveryService]
+///
+/// The handle stays usable after the service is dropped, it keeps the last known sources and
+/// subscriptions disconnect after the initial events.
+#[derive(Debug, Clone)]
+pub struct NDIDiscoveryHandle {
+    shared: Arc<Shared>,
+}
+
+assert_impl_all!(NDIDiscoveryHandle: Send, Sync);
+
+impl NDIDiscoveryHandle {
+    /// The currently known sources
+    pub fn sources(&self) -> Arc<Vec<NDISource>> {
+        self.shared.snapshot()
+    }
+
+    /// Subscribes to changes of the source list
+    ///
+    /// All currently known sources are sent as [NDISourceEvent::Added] first. The channel
+    /// disconnects when the service is dropped.
+    pub fn subscribe(&self) -> mpsc::Receiver<NDISourceEvent> {
+        self.shared.subscribe()
+    }
+}
+
+/// Runs a [NDISourceFinder] on a background thread
+///
+/// The thread is stopped and joined when the service is dropped.
+///
+/// ```rust,no_run
+/// # use ndi_sdk_sys::{discovery::NDIDiscoveryService, find::NDISourceFinderBuilder};
+/// # fn main() {
+/// let finder = NDISourceFinderBuilder::new().build().unwrap();
+/// let service = NDIDiscoveryService::new(finder).unwrap();
+///
+/// let handle = service.handle();
+/// std::thread::spawn(move || {
+///     for event in handle.subscribe() {
+///         println!("{event:?}");
+///     }
+/// });
+///
+/// println!("{} sources", service.handle().sources().len());
+/// # }
+/// ```
+pub struct NDIDiscoveryService {
+    handle: NDIDiscoveryHandle,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+assert_impl_all!(NDIDiscoveryService: Send, Sync);
+
+impl NDIDiscoveryService {
+    /// The default interval in which the thread checks if it should stop
+    pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+    /// Starts the discovery thread, fails if the thread can not be spawned
+    pub fn new(finder: NDISourceFinder) -> std::io::Result<Self> {
+        Self::with_poll_interval(finder, Self::DEFAULT_POLL_INTERVAL)
+    }
+
+    /// Starts the discovery thread with a custom poll interval
+    ///
+    /// The poll interval is the maximum time the thread blocks waiting for changes, it bounds the
+    /// time needed to stop the service.
+    pub fn with_poll_interval(
+        finder: NDISourceFinder,
+        poll_interval: Duration,
+    ) -> std::io::Result<Self> {
+        let shared = Arc::new(Shared::default());
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let shared = shared.clone();
+            let stop = stop.clone();
+            std::thread::Builder::new()
+                .name("ndi-discovery".to_owned())
+                .spawn(move || {
+                    let mut watcher = NDISourceWatcher::new(finder);
+                    while !stop.load(Ordering::Acquire) {
+                        let events = watcher.wait_for_events(poll_interval).value;
+                        if !events.is_empty() {
+                            shared.publish(watcher.sources().to_vec(), events);
+                        }
+                    }
+                })?
+        };
+
+        Ok(NDIDiscoveryService {
+            handle: NDIDiscoveryHandle { shared },
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    /// Returns a handle that can be shared with other threads
+    pub fn handle(&self) -> NDIDiscoveryHandle {
+        self.handle.clone()
+    }
+}
+
+impl Drop for NDIDiscoveryService {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(thread) = self.thread.take() {
+            // A panic on the discovery thread is not propagated into drop
+            let _ = thread.join();
+        }
+        self.handle.shared.stop();
+    }
+}
+
+impl Debug for NDIDiscoveryService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NDIDiscoveryService")
+            .field("sources", &self.handle.sources())
+            .field("stopped", &self.stop.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::find::diff_sources;
+
+    fn source(name: &str) -> NDISource {
+        NDISource::from_name(name).unwrap()
+    }
+
+    fn publish(shared: &Shared, sources: Vec<NDISource>) {
+        let events = diff_sources(&shared.snapshot(), &sources);
+        shared.publish(sources, events);
+    }
+
+    #[test]
+    fn test_subscriptions() {
+        let handle = NDIDiscoveryHandle {
+            shared: Arc::new(Shared::default()),
+        };
+        let a = source("MACHINE (A)");
+        let b = source("MACHINE (B)");
+
+        let early = handle.subscribe();
+        publish(&handle.shared, vec![a.clone()]);
+
+        let late = handle.subscribe();
+        let dropped = handle.subscribe();
+        drop(dropped);
+
+        publish(&handle.shared, vec![b.clone()]);
+        assert_eq!(handle.sources().as_slice(), std::slice::from_ref(&b));
+        assert_eq!(handle.shared.subscribers.lock().unwrap().senders.len(), 2);
+
+        handle.shared.stop();
+
+        assert_eq!(
+            early.iter().collect::<Vec<_>>(),
+            [
+                NDISourceEvent::Added(a.clone()),
+                NDISourceEvent::Removed(a.clone()),
+                NDISourceEvent::Added(b.clone()),
+            ]
+        );
+        assert_eq!(
+            late.iter().collect::<Vec<_>>(),
+            [
+                NDISourceEvent::Added(a.clone()),
+                NDISourceEvent::Removed(a),
+                NDISourceEvent::Added(b.clone()),
+            ]
+        );
+
+        // Subscribing after the service stopped yields the snapshot and disconnects
+        assert_eq!(
+            handle.subscribe().iter().collect::<Vec<_>>(),
+            [NDISourceEvent::Added(b)]
+        );
+    }
+}
@@ -4,7 +4,8 @@ use num_enum::{IntoPrimitive, TryFromPrimitive};
 
 use crate::{
     bindings::{
-        self, NDIlib_frame_format_type_e, NDIlib_recv_bandwidth_e, NDIlib_recv_color_format_e,
+        self, NDIlib_frame_format_type_e, NDIlib_receiver_command_e, NDIlib_receiver_type_e,
+        NDIlib_recv_bandwidth_e, NDIlib_recv_color_format_e,
     },
     four_cc::FourCCVideo,
 };
@@ -18,6 +19,7 @@ use crate::{
 #[non_exhaustive]
 #[allow(non_camel_case_types)]
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive, IntoPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NDIPreferredColorFormat {
     #[default]
     Fastest = bindings::NDIlib_recv_color_format_e_NDIlib_recv_color_format_fastest,
@@ -172,6 +174,7 @@ impl Error for FromFourCCError {}
 #[repr(i32)]
 #[non_exhaustive]
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, TryFromPrimitive, IntoPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NDIBandwidthMode {
     #[default]
     Default = bindings::NDIlib_recv_bandwidth_e_NDIlib_recv_bandwidth_highest,
@@ -199,6 +202,7 @@ impl NDIBandwidthMode {
 #[cfg_attr(not(target_os = "windows"), repr(u32))]
 #[non_exhaustive]
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, TryFromPrimitive, IntoPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NDIFieldedFrameMode {
     /// Progressive (non-fielded) video frame.
     #[default]
@@ -237,6 +241,43 @@ impl NDIFieldedFrameMode {
     }
 }
 
+/// The stream types a remote receiver is receiving, see [crate::listener]
+///
+/// C equivalent: `NDIlib_receiver_type_e`
+#[cfg_attr(target_os = "windows", repr(i32))]
+#[cfg_attr(not(target_os = "windows"), repr(u32))]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, TryFromPrimitive, IntoPrimitive)]
+pub enum NDIReceiverStreamType {
+    Metadata = bindings::NDIlib_receiver_type_e_NDIlib_receiver_type_metadata,
+    Video = bindings::NDIlib_receiver_type_e_NDIlib_receiver_type_video,
+    Audio = bindings::NDIlib_receiver_type_e_NDIlib_receiver_type_audio,
+}
+
+impl NDIReceiverStreamType {
+    pub(crate) fn from_ffi(value: NDIlib_receiver_type_e) -> Option<Self> {
+        Self::try_from_primitive(value).ok()
+    }
+}
+
+/// The commands a remote receiver accepts, see [crate::listener]
+///
+/// C equivalent: `NDIlib_receiver_command_e`
+#[cfg_attr(target_os = "windows", repr(i32))]
+#[cfg_attr(not(target_os = "windows"), repr(u32))]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, TryFromPrimitive, IntoPrimitive)]
+pub enum NDIReceiverCommand {
+    /// The receiver can be told to connect to a different source
+    Connect = bindings::NDIlib_receiver_command_e_NDIlib_receiver_command_connect,
+}
+
+impl NDIReceiverCommand {
+    pub(crate) fn from_ffi(value: NDIlib_receiver_command_e) -> Option<Self> {
+        Self::try_from_primitive(value).ok()
+    }
+}
+
 /// Describes which frame type was received
 #[must_use]
 #[non_exhaustive]
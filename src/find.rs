@@ -13,10 +13,21 @@
 //! <https://docs.ndi.video/all/developing-with-ndi/sdk/ndi-find>
 
 use core::slice;
-use std::time::Duration;
+use std::{
+    collections::HashMap,
+    error::Error,
+    ffi::CString,
+    fmt::Display,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+    time::Duration,
+};
 
 use crate::{
-    bindings, blocking_update::BlockingUpdate, source::NDISourceRef, util::duration_to_ms,
+    bindings,
+    blocking_update::BlockingUpdate,
+    source::{NDISource, NDISourceRef},
+    util::{GroupNameError, duration_to_ms, validate_groups},
 };
 
 /// Builder for [NDISourceFinder]
@@ -24,12 +35,16 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct NDISourceFinderBuilder {
     show_local_sources: bool,
+    groups: Option<CString>,
+    extra_ips: Vec<NDIExtraAddress>,
 }
 
 impl Default for NDISourceFinderBuilder {
     fn default() -> Self {
         Self {
             show_local_sources: true,
+            groups: None,
+            extra_ips: Vec::new(),
         }
     }
 }
@@ -45,11 +60,61 @@ impl NDISourceFinderBuilder {
         self
     }
 
+    /// Only show sources in the given groups, if not set the system default groups are used.
+    ///
+    /// See [crate::sender::NDISenderBuilder::groups] for more information about groups
+    pub fn groups<I, S>(mut self, groups: I) -> Result<Self, GroupNameError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.groups = Some(validate_groups(groups)?);
+        Ok(self)
+    }
+
+    /// Also query the given address for sources, this is needed to find sources in other subnets
+    /// that can not be reached by mDNS.
+    pub fn extra_ip(mut self, address: impl Into<NDIExtraAddress>) -> Self {
+        self.extra_ips.push(address.into());
+        self
+    }
+
+    /// Also query the given addresses for sources, see [NDISourceFinderBuilder::extra_ip]
+    pub fn extra_ips<I>(mut self, addresses: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<NDIExtraAddress>,
+    {
+        self.extra_ips
+            .extend(addresses.into_iter().map(|address| address.into()));
+        self
+    }
+
+    /// Serializes the extra addresses into the comma-separated list expected by the SDK
+    fn extra_ips_list(&self) -> Option<CString> {
+        if self.extra_ips.is_empty() {
+            return None;
+        }
+
+        let list = self
+            .extra_ips
+            .iter()
+            .map(|address| address.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        Some(CString::new(list).expect("[Invariant Error] Extra addresses contain null characters"))
+    }
+
     pub fn build(self) -> Option<NDISourceFinder> {
+        let extra_ips = self.extra_ips_list();
         let options = bindings::NDIlib_find_create_t {
             show_local_sources: self.show_local_sources,
-            p_groups: std::ptr::null(),
-            p_extra_ips: std::ptr::null(),
+            p_groups: self
+                .groups
+                .as_ref()
+                .map_or(std::ptr::null(), |s| s.as_ptr()),
+            p_extra_ips: extra_ips.as_ref().map_or(std::ptr::null(), |s| s.as_ptr()),
         };
         let handle = unsafe { bindings::NDIlib_find_create_v2(&options) };
         if handle.is_null() {
@@ -111,3 +176,315 @@ impl Drop for NDISourceFinder {
         unsafe { bindings::NDIlib_find_destroy(self.handle) }
     }
 }
+
+/// A change in the list of discovered sources, see [NDISourceWatcher]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NDISourceEvent {
+    /// A new source was discovered
+    Added(NDISource),
+    /// A source is no longer available
+    Removed(NDISource),
+    /// A source with the same name is now reachable at a different address
+    AddressChanged { old: NDISource, new: NDISource },
+}
+
+/// Computes the events that turn `old` into `new`, sources are identified by their name.
+///
+/// Events are ordered: removals and address changes in the order of `old`, then additions in the order of `new`.
+pub fn diff_sources(old: &[NDISource], new: &[NDISource]) -> Vec<NDISourceEvent> {
+    let old_by_name: HashMap<&str, &NDISource> = old.iter().map(|s| (s.name(), s)).collect();
+    let new_by_name: HashMap<&str, &NDISource> = new.iter().map(|s| (s.name(), s)).collect();
+
+    let mut events = Vec::new();
+
+    for old_source in old {
+        match new_by_name.get(old_source.name()) {
+            None => events.push(NDISourceEvent::Removed(old_source.clone())),
+            // Names are equal, so the sources differ in their address
+            Some(&new_source) if new_source != old_source => {
+                events.push(NDISourceEvent::AddressChanged {
+                    old: old_source.clone(),
+                    new: new_source.clone(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    for new_source in new {
+        if !old_by_name.contains_key(new_source.name()) {
+            events.push(NDISourceEvent::Added(new_source.clone()));
+        }
+    }
+
+    events
+}
+
+/// Tracks the sources of a [NDISourceFinder] and reports what changed between calls
+///
+/// ```rust,no_run
+/// # use std::time::Duration;
+/// # use ndi_sdk_sys::find::{NDISourceEvent, NDISourceFinderBuilder, NDISourceWatcher};
+/// # fn main() {
+/// let finder = NDISourceFinderBuilder::new().build().unwrap();
+/// let mut watcher = NDISourceWatcher::new(finder);
+/// loop {
+///     for event in watcher.wait_for_events(Duration::from_secs(1)).value {
+///         match event {
+///             NDISourceEvent::Added(source) => println!("Found {}", source.name()),
+///             NDISourceEvent::Removed(source) => println!("Lost {}", source.name()),
+///             event => println!("{event:?}"),
+///         }
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct NDISourceWatcher {
+    finder: NDISourceFinder,
+    sources: Vec<NDISource>,
+}
+
+impl NDISourceWatcher {
+    /// Creates a watcher, the initially known sources are reported as [NDISourceEvent::Added] by the first update
+    pub fn new(finder: NDISourceFinder) -> Self {
+        NDISourceWatcher {
+            finder,
+            sources: Vec::new(),
+        }
+    }
+
+    /// The sources known after the last update
+    pub fn sources(&self) -> &[NDISource] {
+        &self.sources
+    }
+
+    /// Gives back the underlying finder
+    pub fn into_finder(self) -> NDISourceFinder {
+        self.finder
+    }
+
+    /// Fetches the current source list and returns the changes since the last update
+    pub fn update(&mut self) -> Vec<NDISourceEvent> {
+        let current: Vec<NDISource> = match self.finder.get_source_iter() {
+            Some(sources) => sources.map(|source| source.to_owned()).collect(),
+            None => Vec::new(),
+        };
+
+        let events = diff_sources(&self.sources, &current);
+        self.sources = current;
+        events
+    }
+
+    /// Blocks until the source list changes or the timeout is reached, then returns the changes since the last update
+    ///
+    /// The events are also returned when the timeout was reached, this does not miss changes that
+    /// happened between two calls.
+    pub fn wait_for_events(&mut self, timeout: Duration) -> BlockingUpdate<Vec<NDISourceEvent>> {
+        let changed = self.finder.wait_for_change(timeout).value_updated();
+        let events = self.update();
+        BlockingUpdate::new(events, changed)
+    }
+}
+
+/// An additional address that is queried for sources, see [NDISourceFinderBuilder::extra_ip]
+///
+/// Parsing a string accepts IP addresses and host names.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NDIExtraAddress {
+    Ip(IpAddr),
+    Hostname(String),
+}
+
+impl NDIExtraAddress {
+    /// Validates a host name (RFC 1123): at most 253 characters, dot separated labels of up to 63
+    /// letters, digits and hyphens, not starting or ending with a hyphen.
+    pub fn hostname(hostname: &str) -> Result<Self, NDIExtraAddressError> {
+        let hostname = hostname.strip_suffix('.').unwrap_or(hostname);
+
+        let valid_label = |label: &str| {
+            (1..=63).contains(&label.len())
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        };
+
+        if hostname.len() <= 253 && hostname.split('.').all(valid_label) {
+            Ok(Self::Hostname(hostname.to_owned()))
+        } else {
+            Err(NDIExtraAddressError::InvalidHostname(hostname.to_owned()))
+        }
+    }
+}
+
+impl Display for NDIExtraAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ip(ip) => write!(f, "{ip}"),
+            Self::Hostname(hostname) => f.write_str(hostname),
+        }
+    }
+}
+
+impl FromStr for NDIExtraAddress {
+    type Err = NDIExtraAddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        match s.parse::<IpAddr>() {
+            Ok(ip) => Ok(Self::Ip(ip)),
+            Err(_) => Self::hostname(s),
+        }
+    }
+}
+
+impl From<IpAddr> for NDIExtraAddress {
+    fn from(ip: IpAddr) -> Self {
+        Self::Ip(ip)
+    }
+}
+
+impl From<Ipv4Addr> for NDIExtraAddress {
+    fn from(ip: Ipv4Addr) -> Self {
+        Self::Ip(ip.into())
+    }
+}
+
+impl From<Ipv6Addr> for NDIExtraAddress {
+    fn from(ip: Ipv6Addr) -> Self {
+        Self::Ip(ip.into())
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NDIExtraAddressError {
+    /// Neither a valid IP address nor a valid host name
+    InvalidHostname(String),
+}
+
+impl Display for NDIExtraAddressError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidHostname(hostname) => {
+                write!(
+                    f,
+                    "{hostname:?} is neither an IP address nor a valid host name"
+                )
+            }
+        }
+    }
+}
+
+impl Error for NDIExtraAddressError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_groups() {
+        let builder = NDISourceFinderBuilder::new()
+            .groups(["cameras", " studio 1 "])
+            .unwrap();
+        assert_eq!(builder.groups.as_deref(), Some(c"cameras,studio 1"));
+
+        let groups: [&str; 0] = [];
+        assert_eq!(
+            NDISourceFinderBuilder::new().groups(groups).unwrap_err(),
+            GroupNameError::Empty
+        );
+        assert_eq!(
+            NDISourceFinderBuilder::new()
+                .groups(["a", " "])
+                .unwrap_err(),
+            GroupNameError::Empty
+        );
+        assert_eq!(
+            NDISourceFinderBuilder::new().groups(["a,b"]).unwrap_err(),
+            GroupNameError::ContainsComma("a,b".to_owned())
+        );
+        assert!(matches!(
+            NDISourceFinderBuilder::new().groups(["a\0"]),
+            Err(GroupNameError::NulError(_))
+        ));
+    }
+
+    #[test]
+    fn test_extra_ips() {
+        assert_eq!(NDISourceFinderBuilder::new().extra_ips_list(), None);
+
+        let builder = NDISourceFinderBuilder::new()
+            .extra_ip(Ipv4Addr::new(10, 0, 0, 1))
+            .extra_ips([
+                "::1".parse::<NDIExtraAddress>().unwrap(),
+                "ndi-server.local.".parse().unwrap(),
+            ]);
+        assert_eq!(
+            builder.extra_ips_list().as_deref(),
+            Some(c"10.0.0.1,::1,ndi-server.local")
+        );
+    }
+
+    fn source(name: &str) -> NDISource {
+        NDISource::from_name(name).unwrap()
+    }
+
+    #[test]
+    fn test_diff_sources() {
+        let a = source("MACHINE (A)");
+        let b = source("MACHINE (B)");
+        let c = source("MACHINE (C)");
+        let b_moved = unsafe {
+            NDISourceRef::from(bindings::NDIlib_source_t {
+                p_ndi_name: c"MACHINE (B)".as_ptr(),
+                __bindgen_anon_1: bindings::NDIlib_source_t__bindgen_ty_1 {
+                    p_url_address: c"10.0.0.2:5961".as_ptr(),
+                },
+            })
+        }
+        .to_owned();
+
+        assert_eq!(diff_sources(&[], &[]), []);
+        assert_eq!(
+            diff_sources(&[], &[a.clone(), b.clone()]),
+            [
+                NDISourceEvent::Added(a.clone()),
+                NDISourceEvent::Added(b.clone())
+            ]
+        );
+        assert_eq!(
+            diff_sources(&[a.clone(), b.clone()], &[b_moved.clone(), c.clone()]),
+            [
+                NDISourceEvent::Removed(a.clone()),
+                NDISourceEvent::AddressChanged {
+                    old: b.clone(),
+                    new: b_moved.clone()
+                },
+                NDISourceEvent::Added(c.clone()),
+            ]
+        );
+        assert_eq!(diff_sources(&[c.clone(), a.clone()], &[a, c]), []);
+    }
+
+    #[test]
+    fn test_hostname() {
+        assert_eq!(
+            "192.168.1.20".parse(),
+            Ok(NDIExtraAddress::Ip(Ipv4Addr::new(192, 168, 1, 20).into()))
+        );
+        assert!(NDIExtraAddress::hostname("studio-a.example.com").is_ok());
+
+        for invalid in ["", "a,b", "-studio", "studio..local", "white space", "a\0"] {
+            assert_eq!(
+                invalid.parse::<NDIExtraAddress>(),
+                Err(NDIExtraAddressError::InvalidHostname(invalid.to_owned()))
+            );
+        }
+        assert!(NDIExtraAddress::hostname(&"a".repeat(64)).is_err());
+    }
+}
@@ -9,7 +9,7 @@ use num_enum::{IntoPrimitive, TryFromPrimitive};
 
 use crate::{
     bindings::{self, NDIlib_FourCC_audio_type_e, NDIlib_FourCC_video_type_e},
-    buffer_info::BufferInfo,
+    buffer_info::{BufferInfo, PlaneInfo, PlaneKind},
     enums::NDIFieldedFrameMode,
     resolution::Resolution,
     subsampling::Subsampling,
@@ -49,38 +49,92 @@ impl FourCCVideo {
     }
 
     /// Returns information about the memory layout of the frame data
+    ///
+    /// Layouts as defined by the SDK:
+    /// - `UYVY`: packed 4:2:2, 8bit
+    /// - `UYVA`: `UYVY` plane followed by an 8bit alpha plane
+    /// - `P216`: 16bit Y plane followed by an interleaved 16bit UV plane (4:2:2)
+    /// - `PA16`: `P216` planes followed by a 16bit alpha plane
+    /// - `YV12`: 8bit Y plane followed by the V and U planes (4:2:0)
+    /// - `I420`: 8bit Y plane followed by the U and V planes (4:2:0)
+    /// - `NV12`: 8bit Y plane followed by an interleaved UV plane (4:2:0)
+    /// - `RGBA`/`RGBX`/`BGRA`/`BGRX`: packed 4:4:4, 8bit
+    ///
+    /// <https://docs.ndi.video/all/developing-with-ndi/sdk/frame-types#video-frames-ndilib_video_frame_v2_t>
     pub fn buffer_info(
         self,
         resolution: Resolution,
         field_mode: NDIFieldedFrameMode,
     ) -> Result<BufferInfo, BufferInfoError> {
         use FourCCVideo::*;
-        let pixel_stride;
-        let mut subsampling = Subsampling::none();
-
-        match self {
-            UYVY => {
-                pixel_stride = 2;
-                subsampling = Subsampling::new(4, 2, 2);
-            }
-            BGRA | BGRX | RGBA | RGBX => {
-                pixel_stride = 4;
-            }
-            cc => return Err(BufferInfoError::UnsupportedFourCC(cc)),
+        use PlaneKind::*;
+
+        const S422: Subsampling = Subsampling::new(4, 2, 2);
+        const S420: Subsampling = Subsampling::new(4, 2, 0);
+        const NONE: Subsampling = Subsampling::none();
+
+        let width = resolution.x;
+        // a single field only contains every second line
+        let lines = if field_mode.is_single_field() {
+            resolution.y / 2
+        } else {
+            resolution.y
         };
+        let chroma_lines = lines.div_ceil(2);
 
-        let size = resolution.pixels() * pixel_stride;
-
-        Ok(BufferInfo {
-            size: if field_mode.is_single_field() {
-                size / 2
-            } else {
-                size
-            },
-            line_stride: resolution.x * pixel_stride,
-            resolution,
-            field_mode,
-            subsampling,
+        let info = |subsampling, planes: &[PlaneInfo]| {
+            BufferInfo::from_planes(resolution, field_mode, subsampling, planes)
+        };
+        let plane = PlaneInfo::new;
+
+        Ok(match self {
+            UYVY => info(S422, &[plane(Packed, width * 2, lines, S422, 1)]),
+            UYVA => info(
+                S422,
+                &[
+                    plane(Packed, width * 2, lines, S422, 1),
+                    plane(A, width, lines, NONE, 1),
+                ],
+            ),
+            P216 => info(
+                S422,
+                &[
+                    plane(Y, width * 2, lines, NONE, 2),
+                    plane(UV, width * 2, lines, S422, 2),
+                ],
+            ),
+            PA16 => info(
+                S422,
+                &[
+                    plane(Y, width * 2, lines, NONE, 2),
+                    plane(UV, width * 2, lines, S422, 2),
+                    plane(A, width * 2, lines, NONE, 2),
+                ],
+            ),
+            YV12 => info(
+                S420,
+                &[
+                    plane(Y, width, lines, NONE, 1),
+                    plane(V, width / 2, chroma_lines, S420, 1),
+                    plane(U, width / 2, chroma_lines, S420, 1),
+                ],
+            ),
+            I420 => info(
+                S420,
+                &[
+                    plane(Y, width, lines, NONE, 1),
+                    plane(U, width / 2, chroma_lines, S420, 1),
+                    plane(V, width / 2, chroma_lines, S420, 1),
+                ],
+            ),
+            NV12 => info(
+                S420,
+                &[
+                    plane(Y, width, lines, NONE, 1),
+                    plane(UV, width, chroma_lines, S420, 1),
+                ],
+            ),
+            BGRA | BGRX | RGBA | RGBX => info(NONE, &[plane(Packed, width * 4, lines, NONE, 1)]),
         })
     }
 }
@@ -227,6 +281,61 @@ impl TryFrom<FourCC> for FourCCAudio {
     }
 }
 
+/// FourCC codes are (de)serialized in their four character string form (e.g. `"UYVY"`)
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+    use super::{FourCC, FourCCAudio, FourCCVideo};
+
+    impl Serialize for FourCC {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(&self.to_string())
+        }
+    }
+
+    impl<'de> Deserialize<'de> for FourCC {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let code = String::deserialize(deserializer)?;
+            let bytes: [u8; 4] = code
+                .as_bytes()
+                .try_into()
+                .map_err(|_| D::Error::custom(format!("Invalid FourCC {code:?}")))?;
+            Ok(FourCC::from_ffi(i32::from_le_bytes(bytes)))
+        }
+    }
+
+    impl Serialize for FourCCVideo {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            FourCC::from(*self).serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for FourCCVideo {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let four_cc = FourCC::deserialize(deserializer)?;
+            four_cc
+                .as_video()
+                .ok_or_else(|| D::Error::custom(format!("Unsupported video FourCC {four_cc}")))
+        }
+    }
+
+    impl Serialize for FourCCAudio {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            FourCC::from(*self).serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for FourCCAudio {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let four_cc = FourCC::deserialize(deserializer)?;
+            four_cc
+                .as_audio()
+                .ok_or_else(|| D::Error::custom(format!("Unsupported audio FourCC {four_cc}")))
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -237,4 +346,162 @@ mod test {
         assert_eq!(fourcc.to_string(), "RGBA");
         assert_eq!(format!("{:?}", fourcc), "FourCC(RGBA)");
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        assert_eq!(
+            serde_json::to_string(&FourCCVideo::UYVY).unwrap(),
+            r#""UYVY""#
+        );
+        assert_eq!(
+            serde_json::from_str::<FourCCVideo>(r#""NV12""#).unwrap(),
+            FourCCVideo::NV12
+        );
+        // The SDK defines the audio FourCC as `FLTp`
+        assert_eq!(
+            serde_json::to_string(&FourCCAudio::FLTP).unwrap(),
+            r#""FLTp""#
+        );
+        assert_eq!(
+            serde_json::from_str::<FourCCAudio>(r#""FLTp""#).unwrap(),
+            FourCCAudio::FLTP
+        );
+        assert!(serde_json::from_str::<FourCCVideo>(r#""FLTp""#).is_err());
+        assert!(serde_json::from_str::<FourCCVideo>(r#""UYV""#).is_err());
+
+        // Owned input, e.g. from a file or an already parsed value
+        assert_eq!(
+            serde_json::from_reader::<_, FourCCVideo>(&br#""UYVY""#[..]).unwrap(),
+            FourCCVideo::UYVY
+        );
+        assert_eq!(
+            serde_json::from_value::<FourCCVideo>(serde_json::json!("UYVY")).unwrap(),
+            FourCCVideo::UYVY
+        );
+        assert_eq!(
+            serde_json::from_str::<FourCCVideo>(r#""\u0055YVY""#).unwrap(),
+            FourCCVideo::UYVY
+        );
+    }
+
+    fn layout(cc: FourCCVideo, field_mode: NDIFieldedFrameMode) -> BufferInfo {
+        cc.buffer_info(Resolution::new(1920, 1080), field_mode)
+            .unwrap()
+    }
+
+    fn plane_layout(info: &BufferInfo) -> Vec<(usize, usize, usize)> {
+        info.planes()
+            .iter()
+            .map(|p| (p.offset, p.line_stride, p.height))
+            .collect()
+    }
+
+    #[test]
+    fn test_packed_layouts() {
+        let uyvy = layout(FourCCVideo::UYVY, NDIFieldedFrameMode::Progressive);
+        assert_eq!(uyvy.size, 1920 * 1080 * 2);
+        assert_eq!(uyvy.line_stride, 1920 * 2);
+        assert_eq!(plane_layout(&uyvy), [(0, 1920 * 2, 1080)]);
+
+        for cc in [
+            FourCCVideo::RGBA,
+            FourCCVideo::RGBX,
+            FourCCVideo::BGRA,
+            FourCCVideo::BGRX,
+        ] {
+            let rgba = layout(cc, NDIFieldedFrameMode::Progressive);
+            assert_eq!(rgba.size, 1920 * 1080 * 4);
+            assert_eq!(plane_layout(&rgba), [(0, 1920 * 4, 1080)]);
+            assert!(!rgba.subsampling.is_subsampled());
+        }
+    }
+
+    #[test]
+    fn test_alpha_layouts() {
+        let uyva = layout(FourCCVideo::UYVA, NDIFieldedFrameMode::Progressive);
+        assert_eq!(uyva.size, 1920 * 1080 * 3);
+        assert_eq!(uyva.line_stride, 1920 * 2);
+        assert_eq!(
+            plane_layout(&uyva),
+            [(0, 1920 * 2, 1080), (1920 * 1080 * 2, 1920, 1080)]
+        );
+
+        let pa16 = layout(FourCCVideo::PA16, NDIFieldedFrameMode::Progressive);
+        assert_eq!(pa16.size, 1920 * 1080 * 6);
+        assert_eq!(
+            plane_layout(&pa16),
+            [
+                (0, 1920 * 2, 1080),
+                (1920 * 1080 * 2, 1920 * 2, 1080),
+                (1920 * 1080 * 4, 1920 * 2, 1080)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_planar_layouts() {
+        let p216 = layout(FourCCVideo::P216, NDIFieldedFrameMode::Progressive);
+        assert_eq!(p216.size, 1920 * 1080 * 4);
+        assert_eq!(p216.subsampling, Subsampling::new(4, 2, 2));
+        assert_eq!(
+            plane_layout(&p216),
+            [(0, 1920 * 2, 1080), (1920 * 1080 * 2, 1920 * 2, 1080)]
+        );
+
+        let nv12 = layout(FourCCVideo::NV12, NDIFieldedFrameMode::Progressive);
+        assert_eq!(nv12.size, 1920 * 1080 * 3 / 2);
+        assert_eq!(nv12.subsampling, Subsampling::new(4, 2, 0));
+        assert_eq!(
+            plane_layout(&nv12),
+            [(0, 1920, 1080), (1920 * 1080, 1920, 540)]
+        );
+
+        for cc in [FourCCVideo::I420, FourCCVideo::YV12] {
+            let planar = layout(cc, NDIFieldedFrameMode::Progressive);
+            assert_eq!(planar.size, 1920 * 1080 * 3 / 2);
+            assert_eq!(
+                plane_layout(&planar),
+                [
+                    (0, 1920, 1080),
+                    (1920 * 1080, 960, 540),
+                    (1920 * 1080 + 960 * 540, 960, 540)
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_single_field_layouts() {
+        for cc in [
+            FourCCVideo::UYVY,
+            FourCCVideo::UYVA,
+            FourCCVideo::P216,
+            FourCCVideo::PA16,
+            FourCCVideo::YV12,
+            FourCCVideo::I420,
+            FourCCVideo::NV12,
+            FourCCVideo::RGBA,
+        ] {
+            let frame = layout(cc, NDIFieldedFrameMode::Progressive);
+            let field = layout(cc, NDIFieldedFrameMode::Field0);
+            assert_eq!(field.size * 2, frame.size, "{cc:?}");
+            assert_eq!(field.line_stride, frame.line_stride, "{cc:?}");
+            assert_eq!(field.planes().len(), frame.planes().len(), "{cc:?}");
+        }
+    }
+
+    #[test]
+    fn test_planes_are_contiguous() {
+        let info = FourCCVideo::I420
+            .buffer_info(Resolution::new(6, 5), NDIFieldedFrameMode::Progressive)
+            .unwrap();
+        let mut end = 0;
+        for plane in info.planes() {
+            assert_eq!(plane.offset, end);
+            end = plane.range().end;
+        }
+        assert_eq!(end, info.size);
+        assert_eq!(info.plane(1).unwrap().height, 3);
+    }
 }
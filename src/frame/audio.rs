@@ -1,9 +1,24 @@
-use std::sync::Arc;
+use std::error::Error;
+use std::fmt::Debug;
+use std::{
+    ffi::{CStr, CString},
+    sync::Arc,
+};
 
-use super::{NDIFrame, RawBufferManagement, RawFrame, drop_guard::FrameDataDropGuard};
+use super::{
+    NDIFrame, RawBufferManagement, RawFrame, drop_guard::FrameDataDropGuard,
+    video::AlreadyAllocatedError,
+};
 
 pub(crate) use crate::bindings::NDIlib_audio_frame_v3_t as NDIRawAudioFrame;
-use crate::{bindings, four_cc::FourCCAudio, receiver::RawReceiver, sender::RawSender};
+use crate::{
+    bindings,
+    four_cc::{FourCC, FourCCAudio},
+    framesync::RawFrameSync,
+    receiver::RawReceiver,
+    sender::RawSender,
+    timecode::NDITime,
+};
 
 impl RawBufferManagement for NDIRawAudioFrame {
     #[inline]
@@ -18,6 +33,11 @@ impl RawBufferManagement for NDIRawAudioFrame {
         )
     }
 
+    #[inline]
+    unsafe fn drop_with_framesync(&mut self, framesync: &Arc<RawFrameSync>) {
+        unsafe { bindings::NDIlib_framesync_free_audio_v2(framesync.raw_ptr(), self) }
+    }
+
     fn assert_unwritten(&self) {
         assert!(
             self.p_data.is_null(),
@@ -35,9 +55,21 @@ unsafe impl Sync for NDIRawAudioFrame {}
 
 impl RawFrame for NDIRawAudioFrame {}
 
-pub type AudioFrame = NDIFrame<NDIRawAudioFrame>;
+/// An audio frame, the samples are stored as planar 32bit floats (FLTP)
+///
+/// C equivalent: `NDIlib_audio_frame_v3_t`
+///
+/// Per-frame metadata set with [AudioFrame::set_metadata] is owned by the frame.
+pub type AudioFrame = NDIFrame<NDIRawAudioFrame, Option<CString>>;
+
+impl Default for AudioFrame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl AudioFrame {
+    /// Constructs a new audio frame (48kHz stereo, without allocating a sample buffer)
     pub fn new() -> Self {
         let raw = NDIRawAudioFrame {
             sample_rate: 48_000,
@@ -55,11 +87,497 @@ impl AudioFrame {
         Self {
             raw,
             alloc: FrameDataDropGuard::NullPtr,
-            custom_state: (),
+            custom_state: None,
         }
     }
 
+    /// Tries to allocate a sample buffer for the current channel/sample count.
+    ///
+    /// The channels are stored back to back, so the channel stride is `samples * 4` bytes.
+    pub fn try_alloc(&mut self) -> Result<(), AudioFrameAllocationError> {
+        if self.is_allocated() {
+            Err(AudioFrameAllocationError::AlreadyAllocated)?;
+        }
+
+        let stride = self
+            .samples()
+            .checked_mul(size_of::<f32>())
+            .filter(|stride| i32::try_from(*stride).is_ok())
+            .ok_or(AudioFrameAllocationError::TooLarge)?;
+        let samples = (stride / size_of::<f32>())
+            .checked_mul(self.channels())
+            .ok_or(AudioFrameAllocationError::TooLarge)?;
+
+        let (alloc, ptr) = FrameDataDropGuard::new_boxed_samples(samples);
+        self.alloc = alloc;
+        self.raw.p_data = ptr;
+        self.raw.__bindgen_anon_1.channel_stride_in_bytes = stride as i32;
+
+        Ok(())
+    }
+
+    /// Allocates a sample buffer for the audio frame. **Panics** if there is an error.
+    pub fn alloc(&mut self) {
+        self.try_alloc().unwrap();
+    }
+
+    /// Deallocates the sample buffer and the metadata
+    pub fn dealloc(&mut self) {
+        unsafe { self.alloc.drop_buffer(&mut self.raw) };
+        self.raw.p_data = std::ptr::null_mut();
+        self.raw.__bindgen_anon_1.channel_stride_in_bytes = 0;
+        // Metadata of SDK frames was freed with the buffer, owned metadata is dropped here
+        self.raw.p_metadata = std::ptr::null_mut();
+        self.custom_state = None;
+    }
+
+    /// Copies the frame including its metadata into a new, writable sample buffer
+    ///
+    /// All properties (including send/receive time) are kept. Only [FourCCAudio::FLTP] frames can
+    /// be copied.
+    pub fn to_owned(&self) -> Result<AudioFrame, AudioFrameAccessError> {
+        let mut raw = self.raw;
+        raw.p_data = std::ptr::null_mut();
+        raw.p_metadata = std::ptr::null_mut();
+        raw.__bindgen_anon_1.channel_stride_in_bytes = 0;
+        let mut frame = AudioFrame {
+            raw,
+            alloc: FrameDataDropGuard::NullPtr,
+            custom_state: None,
+        };
+
+        if !self.is_allocated() {
+            return Ok(frame);
+        }
+
+        let channels = self.audio_data()?;
+        frame
+            .try_alloc()
+            .expect("[Invariant Error] Allocating a copy of an allocated frame failed");
+        for (dst, src) in frame.audio_data_mut()?.zip(channels) {
+            dst.copy_from_slice(src);
+        }
+        frame.set_metadata(self.metadata().map(CStr::to_owned))?;
+
+        Ok(frame)
+    }
+
+    /// Replaces a frame owned by the SDK (e.g. a received frame) with a writable copy
+    ///
+    /// The SDK buffer is freed immediately, so the frame no longer keeps the receiver alive. Frames that
+    /// are already writable are not copied.
+    pub fn detach(&mut self) -> Result<(), AudioFrameAccessError> {
+        if self.is_allocated() && !self.alloc.is_mut() {
+            *self = self.to_owned()?;
+        }
+        Ok(())
+    }
+
+    /// Checks the buffer invariants, returns the sample pointer, the channel stride in samples and the number of samples in the buffer
+    fn buffer_layout(&self) -> Result<(*mut f32, usize, usize), AudioFrameAccessError> {
+        if !self.is_allocated() {
+            Err(AudioFrameAccessError::NotAllocated)?;
+        }
+
+        if self.four_cc() != Some(FourCCAudio::FLTP) {
+            Err(AudioFrameAccessError::UnsupportedFourCC(self.raw_four_cc()))?;
+        }
+
+        assert!(
+            !self.raw.p_data.is_null(),
+            "[Invariant Error] data pointer does not match allocation"
+        );
+
+        let (channels, samples) = (self.channels(), self.samples());
+        let stride = self.channel_stride();
+        assert!(
+            stride.is_multiple_of(size_of::<f32>())
+                && (channels <= 1 || stride >= samples * size_of::<f32>()),
+            "[Fatal FFI Error] Invalid channel stride"
+        );
+        let stride = stride / size_of::<f32>();
+
+        let len = match channels {
+            0 => 0,
+            channels => (channels - 1) * stride + samples,
+        };
+
+        // owned buffers are f32 aligned, but an empty buffer from the SDK is not guaranteed to be
+        let ptr = if len == 0 {
+            std::ptr::NonNull::dangling().as_ptr()
+        } else {
+            self.raw.p_data.cast::<f32>()
+        };
+        assert!(
+            ptr.is_aligned(),
+            "[Fatal FFI Error] Audio data is not aligned"
+        );
+
+        Ok((ptr, stride, len))
+    }
+
+    /// Read access to the samples, yields one slice per channel
+    ///
+    /// ```rust,no_run
+    /// # use ndi_sdk_sys::frame::audio::AudioFrame;
+    /// # let frame = AudioFrame::new();
+    /// for (channel, samples) in frame.audio_data().unwrap().enumerate() {
+    ///     let peak = samples.iter().fold(0f32, |peak, s| peak.max(s.abs()));
+    ///     println!("channel {channel}: {peak}");
+    /// }
+    /// ```
+    pub fn audio_data(
+        &self,
+    ) -> Result<impl ExactSizeIterator<Item = &[f32]>, AudioFrameAccessError> {
+        let (ptr, stride, len) = self.buffer_layout()?;
+        let samples = self.samples();
+
+        let data = unsafe { std::slice::from_raw_parts(ptr, len) };
+        Ok((0..self.channels()).map(move |channel| {
+            let start = channel * stride;
+            &data[start..start + samples]
+        }))
+    }
+
+    /// Mutable access to the samples, yields one slice per channel
+    pub fn audio_data_mut(
+        &mut self,
+    ) -> Result<impl ExactSizeIterator<Item = &mut [f32]>, AudioFrameAccessError> {
+        let (ptr, stride, len) = self.buffer_layout()?;
+        let samples = self.samples();
+
+        if !self.alloc.is_mut() {
+            Err(AudioFrameAccessError::Readonly)?;
+        }
+
+        let mut rest = unsafe { std::slice::from_raw_parts_mut(ptr, len) };
+        Ok((0..self.channels()).map(move |_| {
+            let split = stride.min(rest.len());
+            let (channel, tail) = std::mem::take(&mut rest).split_at_mut(split);
+            rest = tail;
+            &mut channel[..samples]
+        }))
+    }
+
+    /// Read access to the samples of a single channel
+    pub fn channel_data(&self, channel: usize) -> Result<Option<&[f32]>, AudioFrameAccessError> {
+        Ok(self.audio_data()?.nth(channel))
+    }
+
+    /// Mutable access to the samples of a single channel
+    pub fn channel_data_mut(
+        &mut self,
+        channel: usize,
+    ) -> Result<Option<&mut [f32]>, AudioFrameAccessError> {
+        Ok(self.audio_data_mut()?.nth(channel))
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFrameAllocationError {
+    /// The frame is already allocated
+    /// You have to deallocate it first
+    AlreadyAllocated,
+    /// The buffer size exceeds the limits of the SDK
+    TooLarge,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFrameAccessError {
+    /// It is impossible to get a reference to a sample buffer that does not exist
+    NotAllocated,
+    /// Only possible for {AudioFrame::audio_data_mut} and {AudioFrame::set_metadata} if the buffer
+    /// is not intended to be modified (like a received frame)
+    Readonly,
+    /// Samples can only be accessed for [FourCCAudio::FLTP]
+    UnsupportedFourCC(FourCC),
+}
+
+impl std::fmt::Display for AudioFrameAllocationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AlreadyAllocated => f.write_str("Frame already allocated"),
+            Self::TooLarge => f.write_str("Sample buffer is too large"),
+        }
+    }
+}
+
+impl Error for AudioFrameAllocationError {}
+
+impl std::fmt::Display for AudioFrameAccessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotAllocated => f.write_str("No sample buffer is allocated"),
+            Self::Readonly => f.write_str("Sample buffer is read-only"),
+            Self::UnsupportedFourCC(cc) => write!(f, "Unsupported audio FourCC: {cc}"),
+        }
+    }
+}
+
+impl Error for AudioFrameAccessError {}
+
+// Property accessors
+impl AudioFrame {
     pub fn four_cc(&self) -> Option<FourCCAudio> {
         FourCCAudio::from_ffi(self.raw.FourCC)
     }
+
+    pub fn raw_four_cc(&self) -> FourCC {
+        FourCC::from_ffi(self.raw.FourCC as i32)
+    }
+
+    /// Sample rate in Hz
+    pub fn sample_rate(&self) -> u32 {
+        self.raw.sample_rate.max(0) as u32
+    }
+    /// Sets the sample rate in Hz, this does not affect the buffer layout.
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        self.raw.sample_rate = sample_rate
+            .try_into()
+            .expect("Sample rate exceeds i32::MAX");
+    }
+
+    /// Number of channels
+    pub fn channels(&self) -> usize {
+        self.raw.no_channels.max(0) as usize
+    }
+    /// Sets the number of channels.
+    /// This will fail if the frame is already allocated.
+    pub fn set_channels(&mut self, channels: usize) -> Result<(), AlreadyAllocatedError> {
+        if self.is_allocated() {
+            Err(AlreadyAllocatedError {})
+        } else {
+            self.raw.no_channels = channels.try_into().expect("Channel count exceeds i32::MAX");
+            Ok(())
+        }
+    }
+
+    /// Number of samples per channel
+    pub fn samples(&self) -> usize {
+        self.raw.no_samples.max(0) as usize
+    }
+    /// Sets the number of samples per channel.
+    /// This will fail if the frame is already allocated.
+    pub fn set_samples(&mut self, samples: usize) -> Result<(), AlreadyAllocatedError> {
+        if self.is_allocated() {
+            Err(AlreadyAllocatedError {})
+        } else {
+            self.raw.no_samples = samples.try_into().expect("Sample count exceeds i32::MAX");
+            Ok(())
+        }
+    }
+
+    /// The distance between the start of two channels in bytes
+    pub fn channel_stride(&self) -> usize {
+        unsafe { self.raw.__bindgen_anon_1.channel_stride_in_bytes }.max(0) as usize
+    }
+
+    /// Access the metadata associated with the frame if any
+    pub fn metadata(&self) -> Option<&CStr> {
+        if self.raw.p_metadata.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(self.raw.p_metadata) })
+        }
+    }
+
+    /// Attaches metadata (usually XML) that is sent along with the frame, `None` removes it
+    ///
+    /// The metadata is owned by the frame and freed with the sample buffer ([AudioFrame::dealloc]).
+    /// This is only possible for allocated frames that are not owned by the SDK.
+    pub fn set_metadata(&mut self, metadata: Option<CString>) -> Result<(), AudioFrameAccessError> {
+        if !self.is_allocated() {
+            Err(AudioFrameAccessError::NotAllocated)?;
+        }
+        if !self.alloc.is_mut() {
+            Err(AudioFrameAccessError::Readonly)?;
+        }
+
+        self.raw.p_metadata = metadata
+            .as_ref()
+            .map_or(std::ptr::null_mut(), |m| m.as_ptr() as *mut _);
+        self.custom_state = metadata;
+        Ok(())
+    }
+
+    pub fn send_time(&self) -> NDITime {
+        NDITime::from_ffi(self.raw.timecode)
+    }
+    pub fn set_send_time(&mut self, time: NDITime) {
+        self.raw.timecode = time.to_ffi();
+    }
+
+    pub fn recv_time(&self) -> NDITime {
+        NDITime::from_ffi(self.raw.timestamp)
+    }
+    pub fn set_recv_time(&mut self, time: NDITime) {
+        self.raw.timestamp = time.to_ffi();
+    }
+}
+
+impl Debug for AudioFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AudioFrame {{ ")?;
+
+        write!(
+            f,
+            "{} channels x {} samples @ {}Hz, ",
+            self.raw.no_channels, self.raw.no_samples, self.raw.sample_rate
+        )?;
+
+        if let Some(cc) = self.four_cc() {
+            write!(f, "FourCC: {:?}, ", cc)?;
+        } else {
+            write!(f, "FourCC: {:#x}, ", self.raw.FourCC)?;
+        }
+
+        write!(f, "stride: {}, ", self.channel_stride())?;
+
+        write!(f, "metadata: {:?}, ", self.metadata())?;
+
+        write!(
+            f,
+            "timing: send={:?} recv={:?}, ",
+            self.send_time(),
+            self.recv_time()
+        )?;
+
+        write!(f, "alloc: {:?} @ {:?} }}", self.raw.p_data, self.alloc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::test_util::received_view;
+
+    fn alloc_frame(channels: usize, samples: usize) -> AudioFrame {
+        let mut frame = AudioFrame::new();
+        frame.set_channels(channels).unwrap();
+        frame.set_samples(samples).unwrap();
+        frame.alloc();
+        frame
+    }
+
+    #[test]
+    fn test_alloc() {
+        let mut frame = alloc_frame(4, 1602);
+        assert_eq!(frame.channel_stride(), 1602 * 4);
+        assert!(matches!(&frame.alloc, FrameDataDropGuard::Samples(s) if s.len() == 4 * 1602));
+        assert!(frame.raw.p_data.cast::<f32>().is_aligned());
+        assert!(frame.set_channels(2).is_err());
+        assert!(frame.set_samples(2).is_err());
+        assert_eq!(
+            frame.try_alloc(),
+            Err(AudioFrameAllocationError::AlreadyAllocated)
+        );
+
+        let channels: Vec<_> = frame.audio_data().unwrap().collect();
+        assert_eq!(channels.len(), 4);
+        assert!(channels.iter().all(|c| c.len() == 1602));
+
+        frame.dealloc();
+        assert_eq!(
+            frame.audio_data().err(),
+            Some(AudioFrameAccessError::NotAllocated)
+        );
+        frame.set_samples(10).unwrap();
+        frame.alloc();
+        assert_eq!(frame.channel_data(3).unwrap().unwrap().len(), 10);
+    }
+
+    #[test]
+    fn test_planar_layout() {
+        let mut frame = alloc_frame(3, 4);
+        for (i, channel) in frame.audio_data_mut().unwrap().enumerate() {
+            channel.fill(i as f32);
+        }
+        frame.channel_data_mut(2).unwrap().unwrap()[3] = 0.5;
+        assert!(frame.channel_data_mut(3).unwrap().is_none());
+
+        let data = unsafe { std::slice::from_raw_parts(frame.raw.p_data.cast::<f32>(), 12) };
+        assert_eq!(data, &[0., 0., 0., 0., 1., 1., 1., 1., 2., 2., 2., 0.5]);
+    }
+
+    #[test]
+    fn test_padded_stride() {
+        // received frames may have a channel stride larger than the sample count
+        let mut frame = alloc_frame(2, 8);
+        frame.raw.no_samples = 5;
+        for channel in frame.audio_data_mut().unwrap() {
+            assert_eq!(channel.len(), 5);
+            channel.fill(1.);
+        }
+        let data = unsafe { std::slice::from_raw_parts(frame.raw.p_data.cast::<f32>(), 16) };
+        assert_eq!(&data[5..8], &[0.; 3]);
+        assert_eq!(&data[8..13], &[1.; 5]);
+    }
+
+    #[test]
+    fn test_empty() {
+        let frame = alloc_frame(2, 0);
+        assert!(frame.audio_data().unwrap().all(|c| c.is_empty()));
+        let frame = alloc_frame(0, 10);
+        assert_eq!(frame.audio_data().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_properties() {
+        let mut frame = AudioFrame::new();
+        assert_eq!((frame.sample_rate(), frame.channels()), (48_000, 2));
+        frame.set_sample_rate(44_100);
+        frame.set_send_time(NDITime::from_ffi(1234));
+        assert_eq!(frame.sample_rate(), 44_100);
+        assert_eq!(frame.send_time().to_ffi(), 1234);
+        assert!(frame.metadata().is_none());
+    }
+
+    #[test]
+    fn test_metadata() {
+        let mut frame = AudioFrame::new();
+        assert_eq!(
+            frame.set_metadata(Some(c"<a/>".to_owned())),
+            Err(AudioFrameAccessError::NotAllocated)
+        );
+
+        frame = alloc_frame(2, 4);
+        frame.set_metadata(Some(c"<a/>".to_owned())).unwrap();
+        assert_eq!(frame.metadata(), Some(c"<a/>"));
+        frame.set_metadata(None).unwrap();
+        assert_eq!(frame.metadata(), None);
+
+        frame.set_metadata(Some(c"<b/>".to_owned())).unwrap();
+        frame.dealloc();
+        assert_eq!(frame.metadata(), None);
+        frame.assert_unwritten();
+    }
+
+    #[test]
+    fn test_detach() {
+        let mut source = alloc_frame(2, 3);
+        for (i, channel) in source.audio_data_mut().unwrap().enumerate() {
+            channel.fill(i as f32 + 1.);
+        }
+        source.set_metadata(Some(c"<a/>".to_owned())).unwrap();
+        source.set_sample_rate(44_100);
+
+        let mut frame = received_view(&source);
+        assert!(matches!(
+            frame.audio_data_mut(),
+            Err(AudioFrameAccessError::Readonly)
+        ));
+
+        frame.detach().unwrap();
+        assert!(matches!(frame.alloc, FrameDataDropGuard::Samples(_)));
+        assert_ne!(frame.raw.p_data, source.raw.p_data);
+        assert_eq!(frame.metadata(), Some(c"<a/>"));
+        assert_eq!(frame.sample_rate(), 44_100);
+        let channels: Vec<_> = frame.audio_data().unwrap().collect();
+        assert_eq!(channels, [&[1.; 3][..], &[2.; 3][..]]);
+
+        frame.audio_data_mut().unwrap().next().unwrap().fill(0.);
+        assert_eq!(source.channel_data(0).unwrap().unwrap(), &[1.; 3]);
+    }
 }
@@ -1,14 +1,17 @@
 use std::{ffi::CString, fmt::Debug, sync::Arc};
 
-use crate::{receiver::RawReceiver, sender::RawSender};
+use crate::{framesync::RawFrameSync, receiver::RawReceiver, sender::RawSender};
 
 /// Holds the frame allocation
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq)]
 pub(crate) enum FrameDataDropGuard {
     NullPtr,
     Receiver(Option<Arc<RawReceiver>>),
     Sender(Option<Arc<RawSender>>),
+    FrameSync(Option<Arc<RawFrameSync>>),
     Box(Box<[u8]>),
+    /// Like [FrameDataDropGuard::Box], but aligned for `f32` samples
+    Samples(Box<[f32]>),
     CString(CString),
 }
 
@@ -21,7 +24,9 @@ impl Debug for FrameDataDropGuard {
             Self::NullPtr => write!(f, "NullPtr"),
             Self::Receiver(recv) => f.debug_tuple("Receiver").field(recv).finish(),
             Self::Sender(sender) => f.debug_tuple("Sender").field(sender).finish(),
+            Self::FrameSync(framesync) => f.debug_tuple("FrameSync").field(framesync).finish(),
             Self::Box(data) => write!(f, "Box ({} bytes)", data.len()),
+            Self::Samples(data) => write!(f, "Samples ({} samples)", data.len()),
             Self::CString(cstr) => write!(f, "CString ({})", cstr.to_string_lossy()),
         }
     }
@@ -46,6 +51,13 @@ impl Drop for FrameDataDropGuard {
                 );
             }
 
+            Self::FrameSync(Some(framesync)) => {
+                panic!(
+                    "Attempted to drop FrameDataDropGuard::FrameSync, the frame was not freed correctly: {:?}",
+                    framesync
+                );
+            }
+
             _ => {}
         }
     }
@@ -59,6 +71,13 @@ impl FrameDataDropGuard {
         (FrameDataDropGuard::Box(buf), ptr)
     }
 
+    /// allocate a new `f32` aligned frame hold in a Box
+    pub fn new_boxed_samples(samples: usize) -> (FrameDataDropGuard, *mut u8) {
+        let mut buf = vec![0f32; samples].into_boxed_slice();
+        let ptr = buf.as_mut_ptr().cast::<u8>();
+        (FrameDataDropGuard::Samples(buf), ptr)
+    }
+
     /// Check if the frame is writable by FFI (=it is a NullPtr)
     #[inline]
     pub fn is_ffi_writable(&self) -> bool {
@@ -77,20 +96,14 @@ impl FrameDataDropGuard {
         !matches!(self, FrameDataDropGuard::NullPtr)
     }
 
-    #[inline]
-    pub fn is_from_sdk(&self) -> bool {
-        matches!(
-            self,
-            FrameDataDropGuard::Receiver(_) | FrameDataDropGuard::Sender(_)
-        )
-    }
-
     /// Checks if it is safe to write into the frame data by user code
     #[inline]
     pub fn is_mut(&self) -> bool {
         matches!(
             self,
-            FrameDataDropGuard::Box(_) | FrameDataDropGuard::CString(_)
+            FrameDataDropGuard::Box(_)
+                | FrameDataDropGuard::Samples(_)
+                | FrameDataDropGuard::CString(_)
         )
     }
 
@@ -103,7 +116,10 @@ impl FrameDataDropGuard {
             }
             FrameDataDropGuard::Receiver(_) => "Already written by receiver",
             FrameDataDropGuard::Sender(_) => "Already written by sender",
-            FrameDataDropGuard::Box(_) => "Data is Boxed, intended to be sent",
+            FrameDataDropGuard::FrameSync(_) => "Already written by framesync",
+            FrameDataDropGuard::Box(_) | FrameDataDropGuard::Samples(_) => {
+                "Data is Boxed, intended to be sent"
+            }
             FrameDataDropGuard::CString(_) => "Data is CString, intended to be sent",
         }
     }
@@ -123,6 +139,10 @@ impl FrameDataDropGuard {
         *self = FrameDataDropGuard::Sender(Some(sender));
     }
 
+    pub fn update_from_framesync(&mut self, framesync: Arc<RawFrameSync>) {
+        *self = FrameDataDropGuard::FrameSync(Some(framesync));
+    }
+
     pub fn update_from_receiver(&mut self, recv: Arc<RawReceiver>) {
         *self = FrameDataDropGuard::Receiver(Some(recv));
     }
@@ -137,6 +157,10 @@ impl FrameDataDropGuard {
             && let Some(sender) = sender.take()
         {
             unsafe { raw.drop_with_sender(&sender) };
+        } else if let FrameDataDropGuard::FrameSync(framesync) = self
+            && let Some(framesync) = framesync.take()
+        {
+            unsafe { raw.drop_with_framesync(&framesync) };
         }
 
         // self and with it all owned data is dropped
@@ -147,5 +171,6 @@ impl FrameDataDropGuard {
 pub(crate) trait RawBufferManagement {
     unsafe fn drop_with_recv(&mut self, recv: &Arc<RawReceiver>);
     unsafe fn drop_with_sender(&mut self, sender: &Arc<RawSender>);
+    unsafe fn drop_with_framesync(&mut self, framesync: &Arc<RawFrameSync>);
     fn assert_unwritten(&self);
 }
@@ -1,14 +1,15 @@
 use super::{RawFrame, drop_guard::FrameDataDropGuard};
 
 /// Common base for video/audio/metadata frames. Handles dynamic memory and other things
+///
+/// `C` holds additional owned data of the frame (e.g. the per-frame metadata of video/audio frames)
 pub struct NDIFrame<Raw: RawFrame, C = ()> {
     pub(crate) raw: Raw,
     pub(crate) alloc: FrameDataDropGuard,
-    #[allow(unused)]
     pub(crate) custom_state: C,
 }
 
-impl<Raw: RawFrame> NDIFrame<Raw> {
+impl<Raw: RawFrame, C> NDIFrame<Raw, C> {
     /// Checks if this frame can be written/received to by the SDK
     #[inline]
     pub fn is_ffi_writable(&self) -> bool {
@@ -46,7 +47,7 @@ pub(crate) trait AsFFIWritable<T: RawFrame> {
     fn to_ffi_recv_frame_ptr(&mut self) -> *mut T;
 }
 
-impl<Raw: RawFrame> AsFFIWritable<Raw> for NDIFrame<Raw> {
+impl<Raw: RawFrame, C> AsFFIWritable<Raw> for NDIFrame<Raw, C> {
     fn to_ffi_recv_frame_ptr(&mut self) -> *mut Raw {
         if self.is_ffi_writable() {
             &mut self.raw
@@ -70,7 +71,7 @@ impl<Raw: RawFrame> AsFFIWritable<Raw> for NDIFrame<Raw> {
     }
 }
 
-impl<Raw: RawFrame> AsFFIWritable<Raw> for Option<NDIFrame<Raw>> {
+impl<Raw: RawFrame, C> AsFFIWritable<Raw> for Option<NDIFrame<Raw, C>> {
     fn to_ffi_recv_frame_ptr(&mut self) -> *mut Raw {
         if let Some(frame) = self {
             frame.to_ffi_recv_frame_ptr()
@@ -80,7 +81,7 @@ impl<Raw: RawFrame> AsFFIWritable<Raw> for Option<NDIFrame<Raw>> {
     }
 }
 
-impl<Raw: RawFrame> AsFFIWritable<Raw> for Option<&mut NDIFrame<Raw>> {
+impl<Raw: RawFrame, C> AsFFIWritable<Raw> for Option<&mut NDIFrame<Raw, C>> {
     fn to_ffi_recv_frame_ptr(&mut self) -> *mut Raw {
         if let Some(frame) = self {
             frame.to_ffi_recv_frame_ptr()
@@ -94,7 +95,7 @@ pub(crate) trait AsFFIReadable<T: RawFrame> {
     fn to_ffi_send_frame_ptr(&self) -> Result<*const T, FFIReadablePtrError>;
 }
 
-impl<Raw: RawFrame> AsFFIReadable<Raw> for NDIFrame<Raw> {
+impl<Raw: RawFrame, C> AsFFIReadable<Raw> for NDIFrame<Raw, C> {
     fn to_ffi_send_frame_ptr(&self) -> Result<*const Raw, FFIReadablePtrError> {
         if self.is_ffi_readable() {
             let ptr: *const Raw = &self.raw;
@@ -0,0 +1,392 @@
+//! Interleaved audio buffers and their conversion to/from planar [AudioFrame]s
+//!
+//! This is a pure Rust replacement for `NDIlib_util_audio_to_interleaved_*_v3` and
+//! `NDIlib_util_audio_from_interleaved_*_v3`.
+//!
+//! NDI audio is 32bit float, where `1.0` corresponds to the reference level of +4 dBU.
+//! For integer formats the `reference_level` specifies how many dB above the reference the full
+//! integer range is:
+//! - When sending audio, `0` dB (the default, like in the SDK) is usually the right choice
+//!   (full scale = `1.0`)
+//! - When receiving audio, [RECEIVE_HEADROOM] leaves 20 dB of headroom before clipping
+//!
+//! C equivalent: `NDIlib_audio_frame_interleaved_16s_t`, `NDIlib_audio_frame_interleaved_32s_t`,
+//! `NDIlib_audio_frame_interleaved_32f_t`
+
+use std::error::Error;
+
+use super::audio::{AudioFrame, AudioFrameAccessError};
+use crate::timecode::NDITime;
+
+/// Default reference level for integer formats in dB, matches the SDK defaults
+pub const DEFAULT_REFERENCE_LEVEL: i32 = 0;
+
+/// Reference level in dB for converting received audio to integers
+///
+/// Received float audio may exceed `1.0`, this leaves 20 dB of headroom before clipping.
+/// Use it with [InterleavedAudio::from_frame_with_reference_level].
+pub const RECEIVE_HEADROOM: i32 = 20;
+
+/// A sample type that can be stored in an [InterleavedAudio] buffer
+pub trait InterleavedSample: Copy + Default + Send + Sync + 'static {
+    /// Converts to a float sample, `gain` is the float value of the integer full scale
+    fn to_f32(self, gain: f32) -> f32;
+    /// Converts from a float sample (clamped), `gain` is the float value of the integer full scale
+    fn from_f32(sample: f32, gain: f32) -> Self;
+}
+
+impl InterleavedSample for i16 {
+    #[inline]
+    fn to_f32(self, gain: f32) -> f32 {
+        self as f32 * (gain / 32768.)
+    }
+
+    #[inline]
+    fn from_f32(sample: f32, gain: f32) -> Self {
+        (sample * (32768. / gain))
+            .round()
+            .clamp(i16::MIN as f32, i16::MAX as f32) as i16
+    }
+}
+
+impl InterleavedSample for i32 {
+    #[inline]
+    fn to_f32(self, gain: f32) -> f32 {
+        (self as f64 * (gain as f64 / 2147483648.)) as f32
+    }
+
+    #[inline]
+    fn from_f32(sample: f32, gain: f32) -> Self {
+        // f64 is required to represent the full i32 range, the cast saturates
+        (sample as f64 * (2147483648. / gain as f64)).round() as i32
+    }
+}
+
+/// Floats are passed through, the reference level is ignored
+impl InterleavedSample for f32 {
+    #[inline]
+    fn to_f32(self, _gain: f32) -> f32 {
+        self
+    }
+
+    #[inline]
+    fn from_f32(sample: f32, _gain: f32) -> Self {
+        sample
+    }
+}
+
+/// An interleaved audio buffer (`L R L R ...` for stereo)
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterleavedAudio<S: InterleavedSample> {
+    sample_rate: u32,
+    channels: usize,
+    reference_level: i32,
+    send_time: NDITime,
+    data: Vec<S>,
+}
+
+/// Interleaved 16bit integer audio
+///
+/// C equivalent: `NDIlib_audio_frame_interleaved_16s_t`
+pub type InterleavedAudio16s = InterleavedAudio<i16>;
+
+/// Interleaved 32bit integer audio
+///
+/// C equivalent: `NDIlib_audio_frame_interleaved_32s_t`
+pub type InterleavedAudio32s = InterleavedAudio<i32>;
+
+/// Interleaved 32bit float audio
+///
+/// C equivalent: `NDIlib_audio_frame_interleaved_32f_t`
+pub type InterleavedAudio32f = InterleavedAudio<f32>;
+
+impl<S: InterleavedSample> InterleavedAudio<S> {
+    /// Creates a silent buffer
+    pub fn new(sample_rate: u32, channels: usize, samples: usize) -> Self {
+        Self {
+            sample_rate,
+            channels,
+            reference_level: DEFAULT_REFERENCE_LEVEL,
+            send_time: NDITime::default(),
+            data: vec![S::default(); channels * samples],
+        }
+    }
+
+    /// Wraps existing interleaved samples, the length must be a multiple of the channel count
+    pub fn from_samples(
+        sample_rate: u32,
+        channels: usize,
+        data: Vec<S>,
+    ) -> Result<Self, InterleavedAudioError> {
+        if channels == 0 {
+            Err(InterleavedAudioError::NoChannels)?;
+        }
+        if !data.len().is_multiple_of(channels) {
+            Err(InterleavedAudioError::IncompleteSample {
+                len: data.len(),
+                channels,
+            })?;
+        }
+
+        Ok(Self {
+            sample_rate,
+            channels,
+            reference_level: DEFAULT_REFERENCE_LEVEL,
+            send_time: NDITime::default(),
+            data,
+        })
+    }
+
+    /// Sets the reference level in dB, see the [module documentation](self)
+    pub fn reference_level(mut self, reference_level: i32) -> Self {
+        self.reference_level = reference_level;
+        self
+    }
+
+    pub fn get_reference_level(&self) -> i32 {
+        self.reference_level
+    }
+
+    /// Sample rate in Hz
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Number of samples per channel
+    pub fn samples(&self) -> usize {
+        self.data.len().checked_div(self.channels).unwrap_or(0)
+    }
+
+    pub fn send_time(&self) -> NDITime {
+        self.send_time
+    }
+    pub fn set_send_time(&mut self, time: NDITime) {
+        self.send_time = time;
+    }
+
+    pub fn as_slice(&self) -> &[S] {
+        &self.data
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [S] {
+        &mut self.data
+    }
+
+    pub fn into_inner(self) -> Vec<S> {
+        self.data
+    }
+
+    /// Float value of the integer full scale
+    fn gain(&self) -> f32 {
+        10f32.powf(self.reference_level as f32 / 20.)
+    }
+
+    /// Interleaves a planar audio frame (sample rate and send time are copied)
+    pub fn from_frame(frame: &AudioFrame) -> Result<Self, AudioFrameAccessError> {
+        Self::from_frame_with_reference_level(frame, DEFAULT_REFERENCE_LEVEL)
+    }
+
+    /// Interleaves a planar audio frame using the given reference level
+    pub fn from_frame_with_reference_level(
+        frame: &AudioFrame,
+        reference_level: i32,
+    ) -> Result<Self, AudioFrameAccessError> {
+        let mut audio = Self::new(frame.sample_rate(), frame.channels(), frame.samples())
+            .reference_level(reference_level);
+        audio.send_time = frame.send_time();
+
+        let gain = audio.gain();
+        let channels = audio.channels;
+        for (channel, samples) in frame.audio_data()?.enumerate() {
+            let dst = audio.data.iter_mut().skip(channel).step_by(channels);
+            for (dst, sample) in dst.zip(samples) {
+                *dst = S::from_f32(*sample, gain);
+            }
+        }
+
+        Ok(audio)
+    }
+
+    /// Converts the buffer into a newly allocated planar audio frame
+    ///
+    /// **Panics** if a single channel exceeds the size limits of the SDK.
+    pub fn to_frame(&self) -> AudioFrame {
+        let mut frame = AudioFrame::new();
+        frame.set_sample_rate(self.sample_rate);
+        frame.set_send_time(self.send_time);
+        frame
+            .set_channels(self.channels)
+            .expect("[Invariant Error] Fresh frame is already allocated");
+        frame
+            .set_samples(self.samples())
+            .expect("[Invariant Error] Fresh frame is already allocated");
+        frame.alloc();
+
+        let gain = self.gain();
+        let channels = self.channels;
+        for (channel, samples) in frame
+            .audio_data_mut()
+            .expect("[Invariant Error] Freshly allocated frame is not writable")
+            .enumerate()
+        {
+            let src = self.data.iter().skip(channel).step_by(channels);
+            for (sample, src) in samples.iter_mut().zip(src) {
+                *sample = src.to_f32(gain);
+            }
+        }
+
+        frame
+    }
+}
+
+impl<S: InterleavedSample> TryFrom<&AudioFrame> for InterleavedAudio<S> {
+    type Error = AudioFrameAccessError;
+
+    fn try_from(frame: &AudioFrame) -> Result<Self, Self::Error> {
+        Self::from_frame(frame)
+    }
+}
+
+impl<S: InterleavedSample> From<&InterleavedAudio<S>> for AudioFrame {
+    fn from(audio: &InterleavedAudio<S>) -> Self {
+        audio.to_frame()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterleavedAudioError {
+    /// An interleaved buffer needs at least one channel
+    NoChannels,
+    /// The buffer length is not a multiple of the channel count
+    IncompleteSample { len: usize, channels: usize },
+}
+
+impl std::fmt::Display for InterleavedAudioError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoChannels => f.write_str("Interleaved audio needs at least one channel"),
+            Self::IncompleteSample { len, channels } => write!(
+                f,
+                "Buffer length {len} is not a multiple of the channel count {channels}"
+            ),
+        }
+    }
+}
+
+impl Error for InterleavedAudioError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stereo_frame(left: &[f32], right: &[f32]) -> AudioFrame {
+        let mut frame = AudioFrame::new();
+        frame.set_samples(left.len()).unwrap();
+        frame.alloc();
+        for (channel, samples) in frame.audio_data_mut().unwrap().zip([left, right]) {
+            channel.copy_from_slice(samples);
+        }
+        frame
+    }
+
+    #[test]
+    fn test_interleave_32f() {
+        let mut frame = stereo_frame(&[0.1, 0.2, 0.3], &[-1., -2., -3.]);
+        frame.set_send_time(NDITime::from_ffi(42));
+
+        let audio = InterleavedAudio32f::from_frame(&frame).unwrap();
+        assert_eq!((audio.channels(), audio.samples()), (2, 3));
+        assert_eq!(audio.as_slice(), &[0.1, -1., 0.2, -2., 0.3, -3.]);
+        assert_eq!(audio.send_time(), NDITime::from_ffi(42));
+
+        let back = audio.to_frame();
+        assert_eq!(back.sample_rate(), 48_000);
+        assert_eq!(back.channel_data(0).unwrap().unwrap(), &[0.1, 0.2, 0.3]);
+        assert_eq!(back.channel_data(1).unwrap().unwrap(), &[-1., -2., -3.]);
+    }
+
+    #[test]
+    fn test_16s_reference_level() {
+        let frame = stereo_frame(&[1., -1., 0.5], &[20., -20., 0.]);
+
+        let audio = InterleavedAudio16s::from_frame_with_reference_level(&frame, 0).unwrap();
+        assert_eq!(audio.as_slice(), &[32767, 32767, -32768, -32768, 16384, 0]);
+
+        let audio = InterleavedAudio16s::from_frame(&frame).unwrap();
+        assert_eq!(audio.get_reference_level(), DEFAULT_REFERENCE_LEVEL);
+        assert_eq!(audio.as_slice()[..2], [32767, 32767]);
+
+        // +20dB: full scale is 10.0
+        let audio =
+            InterleavedAudio16s::from_frame_with_reference_level(&frame, RECEIVE_HEADROOM).unwrap();
+        assert_eq!(audio.get_reference_level(), 20);
+        assert_eq!(audio.as_slice(), &[3277, 32767, -3277, -32768, 1638, 0]);
+
+        let back = audio.to_frame();
+        let left = back.channel_data(0).unwrap().unwrap();
+        assert!((left[0] - 1.).abs() < 1e-3, "{left:?}");
+        assert!((left[2] - 0.5).abs() < 1e-3, "{left:?}");
+    }
+
+    #[test]
+    fn test_16s_full_scale_send() {
+        // sending integer audio must match the SDK default of 0 dB: full scale is 1.0
+        let audio =
+            InterleavedAudio16s::from_samples(48_000, 1, vec![i16::MIN, i16::MAX, 0]).unwrap();
+        let frame = audio.to_frame();
+        assert_eq!(
+            frame.channel_data(0).unwrap().unwrap(),
+            &[-1., 32767. / 32768., 0.]
+        );
+    }
+
+    #[test]
+    fn test_32s_round_trip() {
+        let samples = vec![i32::MIN, i32::MAX, 0, 1 << 20, -(1 << 24), 123_456_789];
+        let audio = InterleavedAudio32s::from_samples(44_100, 3, samples.clone())
+            .unwrap()
+            .reference_level(0);
+        let frame = audio.to_frame();
+        assert_eq!((frame.channels(), frame.samples()), (3, 2));
+        assert_eq!(frame.sample_rate(), 44_100);
+        assert_eq!(frame.channel_data(0).unwrap().unwrap(), &[-1., 1. / 2048.]);
+
+        let back = InterleavedAudio32s::from_frame_with_reference_level(&frame, 0).unwrap();
+        for (got, expected) in back.as_slice().iter().zip(&samples) {
+            // f32 only has a 24bit mantissa
+            assert!(got.abs_diff(*expected) <= 128, "{got} != {expected}");
+        }
+    }
+
+    #[test]
+    fn test_from_samples_errors() {
+        assert_eq!(
+            InterleavedAudio16s::from_samples(48_000, 0, vec![]),
+            Err(InterleavedAudioError::NoChannels)
+        );
+        assert_eq!(
+            InterleavedAudio16s::from_samples(48_000, 2, vec![0; 5]),
+            Err(InterleavedAudioError::IncompleteSample {
+                len: 5,
+                channels: 2
+            })
+        );
+        assert_eq!(
+            InterleavedAudio16s::new(48_000, 2, 100).as_slice().len(),
+            200
+        );
+    }
+
+    #[test]
+    fn test_unallocated_frame() {
+        assert_eq!(
+            InterleavedAudio32f::from_frame(&AudioFrame::new()),
+            Err(AudioFrameAccessError::NotAllocated)
+        );
+    }
+}
@@ -1,10 +1,13 @@
 use std::{
-    ffi::{CStr, CString},
+    ffi::{CStr, CString, NulError},
+    fmt::Debug,
     sync::Arc,
 };
 
 pub(crate) use crate::bindings::NDIlib_metadata_frame_t as NDIRawMetadataFrame;
-use crate::{bindings, receiver::RawReceiver, sender::RawSender};
+use crate::{
+    bindings, framesync::RawFrameSync, receiver::RawReceiver, sender::RawSender, timecode::NDITime,
+};
 
 use super::{NDIFrame, RawBufferManagement, RawFrame, drop_guard::FrameDataDropGuard};
 
@@ -19,6 +22,13 @@ impl RawBufferManagement for NDIRawMetadataFrame {
         unsafe { bindings::NDIlib_send_free_metadata(sender.raw_ptr(), self) }
     }
 
+    #[inline]
+    unsafe fn drop_with_framesync(&mut self, _framesync: &Arc<RawFrameSync>) {
+        panic!(
+            "NDIRawMetadataFrame cannot be dropped with a framesync as it cannot be captured by the framesync."
+        )
+    }
+
     fn assert_unwritten(&self) {
         assert!(
             self.p_data.is_null(),
@@ -80,12 +90,63 @@ impl MetadataFrame {
         }
     }
 
-    // TODO: implement editing of metadata frames, otherwise this doesn't make much sense
-    // pub fn dealloc(&mut self) {
-    //     unsafe { self.alloc.drop_buffer(&mut self.raw) };
-    //     self.raw.p_data = std::ptr::null_mut();
-    //     self.raw.length = 0;
-    // }
+    /// Replaces the content of the frame, the timecode is kept
+    pub fn set_data(&mut self, data: CString) {
+        self.dealloc();
+        self.raw.p_data = data.as_ptr() as *mut _;
+        self.raw.length = data
+            .as_bytes_with_nul()
+            .len()
+            .try_into()
+            .expect("Metadata exceeds i32::MAX bytes");
+        self.alloc = FrameDataDropGuard::CString(data);
+    }
+
+    /// Replaces the content of the frame, the timecode is kept
+    pub fn set_str(&mut self, data: &str) -> Result<(), NulError> {
+        self.set_data(CString::new(data)?);
+        Ok(())
+    }
+
+    /// Frees the content of the frame, the timecode is kept
+    pub fn dealloc(&mut self) {
+        unsafe { self.alloc.drop_buffer(&mut self.raw) };
+        self.raw.p_data = std::ptr::null_mut();
+        self.raw.length = 0;
+    }
+
+    /// Copies the frame into an owned frame that can be edited, e.g. to relay a received frame
+    pub fn to_owned(&self) -> MetadataFrame {
+        let mut frame = match self.to_str() {
+            Some(data) => MetadataFrame::from_string(data.to_owned()),
+            None => MetadataFrame::new(),
+        };
+        frame.raw.timecode = self.raw.timecode;
+        frame
+    }
+
+    /// Replaces a frame owned by the SDK (e.g. a received frame) with an owned copy, see [MetadataFrame::to_owned]
+    ///
+    /// The SDK buffer is freed immediately. Frames that are already owned are not copied.
+    pub fn detach(&mut self) {
+        if self.alloc.is_allocated() && !self.alloc.is_mut() {
+            *self = self.to_owned();
+        }
+    }
+
+    /// The timecode of the frame, defaults to [NDITime::SYNTHESIZE]
+    pub fn send_time(&self) -> NDITime {
+        NDITime::from_ffi(self.raw.timecode)
+    }
+    pub fn set_send_time(&mut self, time: NDITime) {
+        self.raw.timecode = time.to_ffi();
+    }
+}
+
+impl Default for MetadataFrame {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl From<CString> for MetadataFrame {
@@ -93,3 +154,71 @@ impl From<CString> for MetadataFrame {
         Self::from_string(cstr)
     }
 }
+
+impl Debug for MetadataFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MetadataFrame")
+            .field("data", &self.to_str())
+            .field("send_time", &self.send_time())
+            .field("alloc", &self.alloc)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::test_util::received_view;
+
+    #[test]
+    fn test_edit() {
+        let mut frame = MetadataFrame::from(c"<a/>".to_owned());
+        frame.set_send_time(NDITime::from_ffi(1234));
+
+        frame.set_str("<b/>").unwrap();
+        assert_eq!(frame.to_str(), Some(c"<b/>"));
+        assert_eq!(frame.raw.length, 5);
+        assert_eq!(frame.send_time(), NDITime::from_ffi(1234));
+        assert!(frame.set_str("<c>\0</c>").is_err());
+
+        frame.dealloc();
+        assert!(!frame.is_allocated());
+        assert_eq!(frame.to_str(), None);
+        assert_eq!(frame.raw.length, 0);
+        assert_eq!(frame.send_time(), NDITime::from_ffi(1234));
+    }
+
+    #[test]
+    fn test_to_owned() {
+        let mut frame = MetadataFrame::new();
+        assert_eq!(frame.send_time(), NDITime::SYNTHESIZE);
+        frame.set_send_time(NDITime::from_ffi(42));
+
+        let empty = frame.to_owned();
+        assert!(!empty.is_allocated());
+        assert_eq!(empty.send_time(), NDITime::from_ffi(42));
+
+        frame.set_data(c"<ndi_tally_echo/>".to_owned());
+        let mut copy = frame.to_owned();
+        assert_eq!(copy.to_str(), Some(c"<ndi_tally_echo/>"));
+        assert_ne!(copy.raw.p_data, frame.raw.p_data);
+
+        copy.set_str("<x/>").unwrap();
+        assert_eq!(frame.to_str(), Some(c"<ndi_tally_echo/>"));
+    }
+
+    #[test]
+    fn test_detach() {
+        let source = MetadataFrame::from(c"<a/>".to_owned());
+
+        let mut frame = received_view(&source);
+        frame.detach();
+        assert!(matches!(frame.alloc, FrameDataDropGuard::CString(_)));
+        assert_ne!(frame.raw.p_data, source.raw.p_data);
+        assert_eq!(frame.to_str(), Some(c"<a/>"));
+
+        let data = frame.raw.p_data;
+        frame.detach();
+        assert_eq!(frame.raw.p_data, data);
+    }
+}
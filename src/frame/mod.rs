@@ -3,7 +3,11 @@
 pub mod audio;
 pub(crate) mod drop_guard;
 pub mod generic;
+pub mod interleaved;
 pub mod metadata;
+pub mod plane;
+#[cfg(test)]
+pub(crate) mod test_util;
 pub mod video;
 
 use crate::frame::drop_guard::RawBufferManagement;
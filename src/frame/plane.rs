@@ -0,0 +1,408 @@
+//! Plane-aware views into video frame buffers
+//!
+//! Obtained via [VideoFrame::planes](super::video::VideoFrame::planes) and
+//! [VideoFrame::planes_mut](super::video::VideoFrame::planes_mut). They use the layout described by
+//! [BufferInfo] and take care of all offset and stride computations.
+
+use crate::buffer_info::{BufferInfo, MAX_PLANES, PlaneInfo, PlaneKind};
+
+/// Read-only view of a single plane
+#[derive(Debug, Clone, Copy)]
+pub struct PlaneRef<'a> {
+    data: &'a [u8],
+    info: PlaneInfo,
+}
+
+impl<'a> PlaneRef<'a> {
+    /// Layout information about this plane. The offset refers to the whole frame buffer.
+    pub fn info(&self) -> &PlaneInfo {
+        &self.info
+    }
+
+    pub fn kind(&self) -> PlaneKind {
+        self.info.kind
+    }
+
+    /// Number of lines
+    pub fn height(&self) -> usize {
+        self.info.height
+    }
+
+    /// The raw bytes of the plane
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Gets a single line of the plane
+    pub fn row(&self, y: usize) -> Option<&'a [u8]> {
+        let stride = self.info.line_stride;
+        self.data.get(y * stride..(y + 1) * stride)
+    }
+
+    /// Iterates over the lines of the plane
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &'a [u8]> + 'a {
+        self.data.chunks_exact(self.info.line_stride)
+    }
+
+    /// Gets a 16bit view of the plane. Returns None for 8bit planes.
+    pub fn samples16(&self) -> Option<Plane16Ref<'a>> {
+        (self.info.bytes_per_sample == 2).then_some(Plane16Ref { plane: *self })
+    }
+}
+
+/// Mutable view of a single plane
+#[derive(Debug)]
+pub struct PlaneMut<'a> {
+    data: &'a mut [u8],
+    info: PlaneInfo,
+}
+
+impl<'a> PlaneMut<'a> {
+    /// Layout information about this plane. The offset refers to the whole frame buffer.
+    pub fn info(&self) -> &PlaneInfo {
+        &self.info
+    }
+
+    pub fn kind(&self) -> PlaneKind {
+        self.info.kind
+    }
+
+    /// Number of lines
+    pub fn height(&self) -> usize {
+        self.info.height
+    }
+
+    /// Reborrows the plane as read-only view
+    pub fn as_plane_ref(&self) -> PlaneRef<'_> {
+        PlaneRef {
+            data: self.data,
+            info: self.info,
+        }
+    }
+
+    /// The raw bytes of the plane
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        self.data
+    }
+
+    /// Gets a single line of the plane
+    pub fn row_mut(&mut self, y: usize) -> Option<&mut [u8]> {
+        let stride = self.info.line_stride;
+        self.data.get_mut(y * stride..(y + 1) * stride)
+    }
+
+    /// Iterates over the lines of the plane
+    pub fn rows_mut(&mut self) -> impl ExactSizeIterator<Item = &mut [u8]> {
+        self.data.chunks_exact_mut(self.info.line_stride)
+    }
+
+    /// Gets a 16bit view of the plane. Returns None for 8bit planes.
+    pub fn samples16_mut(&mut self) -> Option<Plane16Mut<'_>> {
+        (self.info.bytes_per_sample == 2).then_some(Plane16Mut {
+            data: self.data,
+            info: self.info,
+        })
+    }
+}
+
+/// Read-only view of a plane with 16bit samples (P216/PA16)
+///
+/// Samples are stored little endian. The buffer is not guaranteed to be aligned for `u16`,
+/// therefore samples are accessed by value.
+#[derive(Debug, Clone, Copy)]
+pub struct Plane16Ref<'a> {
+    plane: PlaneRef<'a>,
+}
+
+impl<'a> Plane16Ref<'a> {
+    /// Number of samples per line
+    pub fn width(&self) -> usize {
+        self.plane.info.line_stride / 2
+    }
+
+    /// Number of lines
+    pub fn height(&self) -> usize {
+        self.plane.info.height
+    }
+
+    /// Gets a single sample
+    pub fn get(&self, x: usize, y: usize) -> Option<u16> {
+        self.row(y)?.get(x)
+    }
+
+    /// Gets a single line of the plane
+    pub fn row(&self, y: usize) -> Option<Row16<'a>> {
+        self.plane.row(y).map(Row16)
+    }
+
+    /// Iterates over the lines of the plane
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = Row16<'a>> + 'a {
+        self.plane.rows().map(Row16)
+    }
+}
+
+/// Mutable view of a plane with 16bit samples (P216/PA16)
+///
+/// Samples are stored little endian. The buffer is not guaranteed to be aligned for `u16`,
+/// therefore samples are accessed by value.
+#[derive(Debug)]
+pub struct Plane16Mut<'a> {
+    data: &'a mut [u8],
+    info: PlaneInfo,
+}
+
+impl Plane16Mut<'_> {
+    /// Number of samples per line
+    pub fn width(&self) -> usize {
+        self.info.line_stride / 2
+    }
+
+    /// Number of lines
+    pub fn height(&self) -> usize {
+        self.info.height
+    }
+
+    /// Gets a single sample
+    pub fn get(&self, x: usize, y: usize) -> Option<u16> {
+        let stride = self.info.line_stride;
+        Row16(self.data.get(y * stride..(y + 1) * stride)?).get(x)
+    }
+
+    /// Sets a single sample. Returns None if the position is out of bounds.
+    pub fn set(&mut self, x: usize, y: usize, value: u16) -> Option<()> {
+        self.row_mut(y)?.set(x, value)
+    }
+
+    /// Gets a single line of the plane
+    pub fn row_mut(&mut self, y: usize) -> Option<Row16Mut<'_>> {
+        let stride = self.info.line_stride;
+        self.data
+            .get_mut(y * stride..(y + 1) * stride)
+            .map(Row16Mut)
+    }
+
+    /// Iterates over the lines of the plane
+    pub fn rows_mut(&mut self) -> impl ExactSizeIterator<Item = Row16Mut<'_>> {
+        self.data
+            .chunks_exact_mut(self.info.line_stride)
+            .map(Row16Mut)
+    }
+}
+
+/// A line of 16bit samples
+#[derive(Debug, Clone, Copy)]
+pub struct Row16<'a>(&'a [u8]);
+
+impl<'a> Row16<'a> {
+    /// Number of samples
+    pub fn len(&self) -> usize {
+        self.0.len() / 2
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<u16> {
+        let bytes = self.0.get(index * 2..index * 2 + 2)?;
+        Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = u16> + 'a {
+        self.0
+            .chunks_exact(2)
+            .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+}
+
+/// A mutable line of 16bit samples
+#[derive(Debug)]
+pub struct Row16Mut<'a>(&'a mut [u8]);
+
+impl Row16Mut<'_> {
+    /// Number of samples
+    pub fn len(&self) -> usize {
+        self.0.len() / 2
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<u16> {
+        Row16(self.0).get(index)
+    }
+
+    /// Sets a single sample. Returns None if the index is out of bounds.
+    pub fn set(&mut self, index: usize, value: u16) -> Option<()> {
+        self.0
+            .get_mut(index * 2..index * 2 + 2)?
+            .copy_from_slice(&value.to_le_bytes());
+        Some(())
+    }
+
+    /// Fills the line from the given samples, stops at the shorter of both
+    pub fn copy_from(&mut self, samples: impl IntoIterator<Item = u16>) {
+        for (bytes, value) in self.0.chunks_exact_mut(2).zip(samples) {
+            bytes.copy_from_slice(&value.to_le_bytes());
+        }
+    }
+}
+
+/// All planes of a video frame, see [VideoPlanes::get] or the shortcuts like [VideoPlanes::y]
+///
+/// `P` is either [PlaneRef] or [PlaneMut]
+#[derive(Debug)]
+pub struct VideoPlanes<P> {
+    planes: [Option<P>; MAX_PLANES],
+    info: BufferInfo,
+}
+
+impl<P> VideoPlanes<P> {
+    /// Layout information about the whole frame buffer
+    pub fn buffer_info(&self) -> &BufferInfo {
+        &self.info
+    }
+
+    fn index_of(&self, kind: PlaneKind) -> Option<usize> {
+        self.info
+            .planes()
+            .iter()
+            .position(|plane| plane.kind == kind)
+    }
+
+    /// Gets the plane of the given kind. Returns None if the format has no such plane (or if it was taken).
+    pub fn get(&self, kind: PlaneKind) -> Option<&P> {
+        self.planes[self.index_of(kind)?].as_ref()
+    }
+
+    /// Gets the plane of the given kind. Returns None if the format has no such plane (or if it was taken).
+    pub fn get_mut(&mut self, kind: PlaneKind) -> Option<&mut P> {
+        let index = self.index_of(kind)?;
+        self.planes[index].as_mut()
+    }
+
+    /// Moves the plane of the given kind out, this allows to use multiple [PlaneMut]s at the same time.
+    pub fn take(&mut self, kind: PlaneKind) -> Option<P> {
+        let index = self.index_of(kind)?;
+        self.planes[index].take()
+    }
+
+    /// Iterates over all planes in memory order
+    pub fn iter(&self) -> impl Iterator<Item = &P> {
+        self.planes.iter().flatten()
+    }
+
+    /// Packed plane (UYVY, UYVA, RGBA/RGBX, BGRA/BGRX)
+    pub fn packed(&self) -> Option<&P> {
+        self.get(PlaneKind::Packed)
+    }
+
+    /// Luma plane (P216, PA16, NV12, I420, YV12)
+    pub fn y(&self) -> Option<&P> {
+        self.get(PlaneKind::Y)
+    }
+
+    /// Interleaved chroma plane (P216, PA16, NV12)
+    pub fn uv(&self) -> Option<&P> {
+        self.get(PlaneKind::UV)
+    }
+
+    /// Cb plane (I420, YV12)
+    pub fn u(&self) -> Option<&P> {
+        self.get(PlaneKind::U)
+    }
+
+    /// Cr plane (I420, YV12)
+    pub fn v(&self) -> Option<&P> {
+        self.get(PlaneKind::V)
+    }
+
+    /// Alpha plane (UYVA, PA16)
+    pub fn a(&self) -> Option<&P> {
+        self.get(PlaneKind::A)
+    }
+}
+
+impl<P> IntoIterator for VideoPlanes<P> {
+    type Item = P;
+    type IntoIter = std::iter::Flatten<std::array::IntoIter<Option<P>, MAX_PLANES>>;
+
+    /// Iterates over all (remaining) planes in memory order
+    fn into_iter(self) -> Self::IntoIter {
+        self.planes.into_iter().flatten()
+    }
+}
+
+impl<'a> VideoPlanes<PlaneRef<'a>> {
+    pub(crate) fn new(data: &'a [u8], info: BufferInfo) -> Self {
+        assert_eq!(
+            data.len(),
+            info.size,
+            "[Invariant Error] Buffer size does not match the layout"
+        );
+
+        let mut planes = [None; MAX_PLANES];
+        for (slot, plane) in planes.iter_mut().zip(info.planes()) {
+            *slot = Some(PlaneRef {
+                data: &data[plane.range()],
+                info: *plane,
+            });
+        }
+
+        VideoPlanes { planes, info }
+    }
+}
+
+impl<'a> VideoPlanes<PlaneMut<'a>> {
+    pub(crate) fn new_mut(mut data: &'a mut [u8], info: BufferInfo) -> Self {
+        assert_eq!(
+            data.len(),
+            info.size,
+            "[Invariant Error] Buffer size does not match the layout"
+        );
+
+        let mut planes = std::array::from_fn(|_| None);
+        for (slot, plane) in planes.iter_mut().zip(info.planes()) {
+            // planes are contiguous and stored in memory order
+            let (head, tail) = std::mem::take(&mut data).split_at_mut(plane.size());
+            data = tail;
+            *slot = Some(PlaneMut {
+                data: head,
+                info: *plane,
+            });
+        }
+
+        VideoPlanes { planes, info }
+    }
+
+    /// Packed plane (UYVY, UYVA, RGBA/RGBX, BGRA/BGRX)
+    pub fn packed_mut(&mut self) -> Option<&mut PlaneMut<'a>> {
+        self.get_mut(PlaneKind::Packed)
+    }
+
+    /// Luma plane (P216, PA16, NV12, I420, YV12)
+    pub fn y_mut(&mut self) -> Option<&mut PlaneMut<'a>> {
+        self.get_mut(PlaneKind::Y)
+    }
+
+    /// Interleaved chroma plane (P216, PA16, NV12)
+    pub fn uv_mut(&mut self) -> Option<&mut PlaneMut<'a>> {
+        self.get_mut(PlaneKind::UV)
+    }
+
+    /// Cb plane (I420, YV12)
+    pub fn u_mut(&mut self) -> Option<&mut PlaneMut<'a>> {
+        self.get_mut(PlaneKind::U)
+    }
+
+    /// Cr plane (I420, YV12)
+    pub fn v_mut(&mut self) -> Option<&mut PlaneMut<'a>> {
+        self.get_mut(PlaneKind::V)
+    }
+
+    /// Alpha plane (UYVA, PA16)
+    pub fn a_mut(&mut self) -> Option<&mut PlaneMut<'a>> {
+        self.get_mut(PlaneKind::A)
+    }
+}
@@ -0,0 +1,16 @@
+//! Fixtures shared by the frame tests
+
+use super::{NDIFrame, RawFrame, drop_guard::FrameDataDropGuard};
+
+/// A read-only view of `source` that stands in for a received frame
+///
+/// The buffer stays owned by `source`, so the view must not outlive it.
+pub(crate) fn received_view<Raw: RawFrame + Copy, C: Default>(
+    source: &NDIFrame<Raw, C>,
+) -> NDIFrame<Raw, C> {
+    NDIFrame {
+        raw: source.raw,
+        alloc: FrameDataDropGuard::Receiver(None),
+        custom_state: C::default(),
+    }
+}
@@ -1,7 +1,10 @@
 use num::ToPrimitive;
 use std::error::Error;
 use std::fmt::Debug;
-use std::{ffi::CStr, sync::Arc};
+use std::{
+    ffi::{CStr, CString},
+    sync::Arc,
+};
 
 use num::Rational32;
 
@@ -12,12 +15,17 @@ use crate::{
     buffer_info::BufferInfo,
     enums::NDIFieldedFrameMode,
     four_cc::{BufferInfoError, FourCC, FourCCVideo},
+    framesync::RawFrameSync,
     resolution::Resolution,
     sender::RawSender,
     timecode::NDITime,
 };
 
-use super::{NDIFrame, RawBufferManagement, RawFrame, drop_guard::FrameDataDropGuard};
+use super::{
+    NDIFrame, RawBufferManagement, RawFrame,
+    drop_guard::FrameDataDropGuard,
+    plane::{PlaneMut, PlaneRef, VideoPlanes},
+};
 
 impl RawBufferManagement for NDIRawVideoFrame {
     #[inline]
@@ -32,6 +40,11 @@ impl RawBufferManagement for NDIRawVideoFrame {
         )
     }
 
+    #[inline]
+    unsafe fn drop_with_framesync(&mut self, framesync: &Arc<RawFrameSync>) {
+        unsafe { bindings::NDIlib_framesync_free_video(framesync.raw_ptr(), self) }
+    }
+
     fn assert_unwritten(&self) {
         assert!(
             self.p_data.is_null(),
@@ -52,7 +65,9 @@ impl RawFrame for NDIRawVideoFrame {}
 /// A Video frame
 ///
 /// C equivalent: `NDIlib_video_frame_v2_t`
-pub type VideoFrame = NDIFrame<NDIRawVideoFrame>;
+///
+/// Per-frame metadata set with [VideoFrame::set_metadata] is owned by the frame.
+pub type VideoFrame = NDIFrame<NDIRawVideoFrame, Option<CString>>;
 
 impl Default for VideoFrame {
     fn default() -> Self {
@@ -82,7 +97,7 @@ impl VideoFrame {
         Self {
             raw,
             alloc: FrameDataDropGuard::NullPtr,
-            custom_state: (),
+            custom_state: None,
         }
     }
 
@@ -118,15 +133,54 @@ impl VideoFrame {
         self.try_alloc().unwrap();
     }
 
-    /// Deallocates the frame buffer
+    /// Deallocates the frame buffer and the metadata
     pub fn dealloc(&mut self) {
-        let drops_metadata = self.alloc.is_from_sdk();
         unsafe { self.alloc.drop_buffer(&mut self.raw) };
         self.raw.p_data = std::ptr::null_mut();
         self.raw.__bindgen_anon_1.line_stride_in_bytes = -1;
-        if drops_metadata {
-            self.raw.p_metadata = std::ptr::null_mut();
+        // Metadata of SDK frames was freed with the buffer, owned metadata is dropped here
+        self.raw.p_metadata = std::ptr::null_mut();
+        self.custom_state = None;
+    }
+
+    /// Copies the frame including its metadata into a new, writable frame buffer
+    ///
+    /// All properties (including send/receive time) are kept. Fails if the buffer size cannot
+    /// be determined, e.g. for unknown FourCCs.
+    pub fn to_owned(&self) -> Result<VideoFrame, VideoFrameAccessError> {
+        let mut raw = self.raw;
+        raw.p_data = std::ptr::null_mut();
+        raw.p_metadata = std::ptr::null_mut();
+        raw.__bindgen_anon_1.line_stride_in_bytes = -1;
+        let mut frame = VideoFrame {
+            raw,
+            alloc: FrameDataDropGuard::NullPtr,
+            custom_state: None,
+        };
+
+        if !self.is_allocated() {
+            return Ok(frame);
+        }
+
+        let (data, _) = self.video_data()?;
+        frame
+            .try_alloc()
+            .expect("[Invariant Error] Allocating a copy of an allocated frame failed");
+        frame.video_data_mut()?.0.copy_from_slice(data);
+        frame.set_metadata(self.metadata().map(CStr::to_owned))?;
+
+        Ok(frame)
+    }
+
+    /// Replaces a frame owned by the SDK (e.g. a received frame) with a writable copy
+    ///
+    /// The SDK buffer is freed immediately, so the frame no longer keeps the receiver alive. Frames that
+    /// are already writable are not copied.
+    pub fn detach(&mut self) -> Result<(), VideoFrameAccessError> {
+        if self.is_allocated() && !self.alloc.is_mut() {
+            *self = self.to_owned()?;
         }
+        Ok(())
     }
 
     /// Read access to the frame data
@@ -185,6 +239,31 @@ impl VideoFrame {
             info,
         ))
     }
+
+    /// Read access to the individual planes of the frame data
+    ///
+    /// ```rust,no_run
+    /// # use ndi_sdk_sys::frame::video::VideoFrame;
+    /// # let frame = VideoFrame::new();
+    /// let planes = frame.planes().unwrap();
+    /// if let Some(luma) = planes.y() {
+    ///     for row in luma.rows() {
+    ///         // ...
+    ///     }
+    /// }
+    /// ```
+    pub fn planes(&self) -> Result<VideoPlanes<PlaneRef<'_>>, VideoFrameAccessError> {
+        let (data, info) = self.video_data()?;
+        Ok(VideoPlanes::new(data, info))
+    }
+
+    /// Mutable access to the individual planes of the frame data
+    ///
+    /// Use [VideoPlanes::take] to access multiple planes at the same time.
+    pub fn planes_mut(&mut self) -> Result<VideoPlanes<PlaneMut<'_>>, VideoFrameAccessError> {
+        let (data, info) = self.video_data_mut()?;
+        Ok(VideoPlanes::new_mut(data, info))
+    }
 }
 
 #[non_exhaustive]
@@ -202,8 +281,8 @@ pub enum VideoFrameAllocationError {
 pub enum VideoFrameAccessError {
     /// It is impossible to get a reference to a frame buffer that does not exist
     NotAllocated,
-    /// Only possible for {VideoFrame::video_data_mut} if the buffer is not
-    /// intended to be modified (like a received frame)
+    /// Only possible for {VideoFrame::video_data_mut} and {VideoFrame::set_metadata} if the buffer
+    /// is not intended to be modified (like a received frame)
     Readonly,
     /// An error occurred while trying to compute the buffer info
     BufferInfoError(BufferInfoError),
@@ -310,7 +389,26 @@ impl VideoFrame {
             Some(unsafe { CStr::from_ptr(self.raw.p_metadata) })
         }
     }
-    // TODO: pub fn set_metadata
+
+    /// Attaches metadata (usually XML) that is sent along with the frame, `None` removes it
+    ///
+    /// The metadata is owned by the frame and freed with the frame buffer ([VideoFrame::dealloc]), so
+    /// it stays valid during [crate::sender::NDISender::send_video_async].
+    /// This is only possible for allocated frames that are not owned by the SDK.
+    pub fn set_metadata(&mut self, metadata: Option<CString>) -> Result<(), VideoFrameAccessError> {
+        if !self.is_allocated() {
+            Err(VideoFrameAccessError::NotAllocated)?;
+        }
+        if !self.alloc.is_mut() {
+            Err(VideoFrameAccessError::Readonly)?;
+        }
+
+        self.raw.p_metadata = metadata
+            .as_ref()
+            .map_or(std::ptr::null_mut(), |m| m.as_ptr() as *mut _);
+        self.custom_state = metadata;
+        Ok(())
+    }
 
     /// gets the current field mode of the frame
     pub fn field_mode(&self) -> NDIFieldedFrameMode {
@@ -455,3 +553,183 @@ impl Debug for VideoFrame {
         write!(f, "alloc: {:?} @ {:?} }}", self.raw.p_data, self.alloc)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{buffer_info::PlaneKind, frame::test_util::received_view};
+
+    #[test]
+    fn test_alloc_all_formats() {
+        for cc in [
+            FourCCVideo::UYVY,
+            FourCCVideo::UYVA,
+            FourCCVideo::P216,
+            FourCCVideo::PA16,
+            FourCCVideo::YV12,
+            FourCCVideo::I420,
+            FourCCVideo::NV12,
+            FourCCVideo::RGBA,
+            FourCCVideo::RGBX,
+            FourCCVideo::BGRA,
+            FourCCVideo::BGRX,
+        ] {
+            let mut frame = VideoFrame::new();
+            frame.set_resolution(Resolution::new(1280, 720)).unwrap();
+            frame.set_four_cc(cc).unwrap();
+            frame.try_alloc().unwrap();
+
+            let expected = frame.buffer_info().unwrap();
+            let (data, info) = frame.video_data_mut().unwrap();
+            assert_eq!(info, expected);
+            assert_eq!(data.len(), info.size, "{cc:?}");
+            assert_eq!(frame.lib_stride() as usize, info.line_stride, "{cc:?}");
+        }
+    }
+
+    fn alloc_frame(cc: FourCCVideo) -> VideoFrame {
+        let mut frame = VideoFrame::new();
+        frame.set_resolution(Resolution::new(8, 4)).unwrap();
+        frame.set_four_cc(cc).unwrap();
+        frame.alloc();
+        frame
+    }
+
+    #[test]
+    fn test_planes_i420() {
+        let mut frame = alloc_frame(FourCCVideo::I420);
+
+        let mut planes = frame.planes_mut().unwrap();
+        assert!(planes.packed().is_none() && planes.uv().is_none() && planes.a().is_none());
+        let mut u = planes.take(PlaneKind::U).unwrap();
+        let mut v = planes.take(PlaneKind::V).unwrap();
+        assert!(planes.u().is_none());
+        assert_eq!(u.rows_mut().len(), 2);
+        for row in u.rows_mut() {
+            assert_eq!(row.len(), 4);
+            row.fill(1);
+        }
+        v.row_mut(1).unwrap().fill(2);
+        planes.y_mut().unwrap().row_mut(3).unwrap()[7] = 3;
+
+        let (data, _) = frame.video_data().unwrap();
+        assert_eq!(data[8 * 3 + 7], 3);
+        assert_eq!(&data[32..40], &[1; 8]);
+        assert_eq!(&data[40..44], &[0; 4]);
+        assert_eq!(&data[44..48], &[2; 4]);
+
+        let planes = frame.planes().unwrap();
+        assert_eq!(planes.iter().count(), 3);
+        assert_eq!(planes.v().unwrap().row(1).unwrap(), &[2; 4]);
+        assert!(planes.v().unwrap().row(2).is_none());
+    }
+
+    #[test]
+    fn test_planes_yv12_order() {
+        let mut frame = alloc_frame(FourCCVideo::YV12);
+        frame
+            .planes_mut()
+            .unwrap()
+            .v_mut()
+            .unwrap()
+            .as_bytes_mut()
+            .fill(9);
+
+        let (data, info) = frame.video_data().unwrap();
+        assert_eq!(info.plane(1).unwrap().kind, PlaneKind::V);
+        assert_eq!(&data[32..40], &[9; 8]);
+        assert_eq!(&data[40..48], &[0; 8]);
+    }
+
+    #[test]
+    fn test_planes_pa16() {
+        let mut frame = alloc_frame(FourCCVideo::PA16);
+
+        let mut planes = frame.planes_mut().unwrap();
+        let mut alpha = planes.a_mut().unwrap().samples16_mut().unwrap();
+        assert_eq!((alpha.width(), alpha.height()), (8, 4));
+        alpha.set(7, 3, 0xABCD).unwrap();
+        assert!(alpha.set(8, 3, 0).is_none());
+        assert_eq!(alpha.get(7, 3), Some(0xABCD));
+
+        let mut uv = planes.uv_mut().unwrap().samples16_mut().unwrap();
+        for mut row in uv.rows_mut() {
+            row.copy_from([1, 2].into_iter().cycle());
+        }
+
+        let planes = frame.planes().unwrap();
+        let uv = planes.uv().unwrap().samples16().unwrap();
+        assert!(uv.rows().all(|row| row.iter().eq([1, 2, 1, 2, 1, 2, 1, 2])));
+        let alpha = planes.a().unwrap().samples16().unwrap();
+        assert_eq!(alpha.get(7, 3), Some(0xABCD));
+        assert_eq!(alpha.row(0).unwrap().len(), 8);
+
+        let (data, _) = frame.video_data().unwrap();
+        assert_eq!(&data[data.len() - 2..], &[0xCD, 0xAB]);
+    }
+
+    #[test]
+    fn test_planes_8bit_have_no_16bit_view() {
+        let frame = alloc_frame(FourCCVideo::UYVA);
+        let planes = frame.planes().unwrap();
+        assert!(planes.packed().unwrap().samples16().is_none());
+        assert_eq!(planes.a().unwrap().as_bytes().len(), 8 * 4);
+    }
+
+    #[test]
+    fn test_metadata() {
+        let mut frame = VideoFrame::new();
+        assert_eq!(
+            frame.set_metadata(Some(c"<a/>".to_owned())),
+            Err(VideoFrameAccessError::NotAllocated)
+        );
+
+        frame = alloc_frame(FourCCVideo::UYVY);
+        frame.set_metadata(Some(c"<a/>".to_owned())).unwrap();
+        assert_eq!(frame.metadata(), Some(c"<a/>"));
+        frame.set_metadata(Some(c"<b/>".to_owned())).unwrap();
+        assert_eq!(frame.metadata(), Some(c"<b/>"));
+        frame.set_metadata(None).unwrap();
+        assert_eq!(frame.metadata(), None);
+
+        frame.set_metadata(Some(c"<c/>".to_owned())).unwrap();
+        frame.dealloc();
+        assert_eq!(frame.metadata(), None);
+        frame.assert_unwritten();
+    }
+
+    #[test]
+    fn test_detach() {
+        let mut source = alloc_frame(FourCCVideo::UYVY);
+        source.video_data_mut().unwrap().0.fill(7);
+        source.set_metadata(Some(c"<a/>".to_owned())).unwrap();
+        source.set_recv_time(NDITime::from_ffi(42));
+
+        let mut frame = received_view(&source);
+        assert_eq!(
+            frame.video_data_mut().err(),
+            Some(VideoFrameAccessError::Readonly)
+        );
+
+        frame.detach().unwrap();
+        assert!(matches!(frame.alloc, FrameDataDropGuard::Box(_)));
+        assert_ne!(frame.raw.p_data, source.raw.p_data);
+        assert_ne!(frame.raw.p_metadata, source.raw.p_metadata);
+        assert_eq!(frame.metadata(), Some(c"<a/>"));
+        assert_eq!(frame.recv_time(), NDITime::from_ffi(42));
+        assert_eq!(frame.resolution(), Resolution::new(8, 4));
+
+        let (data, _) = frame.video_data_mut().unwrap();
+        assert!(data.iter().all(|b| *b == 7));
+        data.fill(1);
+        assert!(source.video_data().unwrap().0.iter().all(|b| *b == 7));
+
+        // writable frames are kept as they are
+        let data = frame.raw.p_data;
+        frame.detach().unwrap();
+        assert_eq!(frame.raw.p_data, data);
+
+        let empty = VideoFrame::new().to_owned().unwrap();
+        assert!(!empty.is_allocated());
+    }
+}
@@ -0,0 +1,187 @@
+//! NDI FrameSync
+//!
+//! The frame synchronizer pulls frames from a receiver on the clock of the caller instead of the
+//! clock of the sender: video frames are repeated or dropped and audio is resampled dynamically.
+//! This is what you want for playout with a fixed output clock (e.g. a GPU/SDI output).
+//!
+//! <https://docs.ndi.video/all/developing-with-ndi/sdk/frame-synchronization>
+
+use std::{fmt::Debug, ptr::NonNull, sync::Arc};
+
+use static_assertions::assert_impl_all;
+
+use crate::{
+    bindings,
+    enums::NDIFieldedFrameMode,
+    frame::{audio::AudioFrame, generic::AsFFIWritable, video::VideoFrame},
+    receiver::{NDIReceiver, RawReceiver},
+};
+
+pub(crate) struct RawFrameSync {
+    handle: NonNull<bindings::NDIlib_framesync_instance_type>,
+    /// The receiver must outlive the framesync instance
+    _recv: Arc<RawReceiver>,
+}
+
+impl RawFrameSync {
+    pub(crate) fn raw_ptr(&self) -> bindings::NDIlib_framesync_instance_t {
+        self.handle.as_ptr()
+    }
+}
+
+impl PartialEq for RawFrameSync {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
+
+impl Eq for RawFrameSync {}
+
+impl Drop for RawFrameSync {
+    fn drop(&mut self) {
+        unsafe { bindings::NDIlib_framesync_destroy(self.raw_ptr()) };
+    }
+}
+
+impl Debug for RawFrameSync {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RawFrameSync")
+            .field("raw_ptr", &self.raw_ptr())
+            .finish()
+    }
+}
+
+unsafe impl Send for RawFrameSync {}
+unsafe impl Sync for RawFrameSync {}
+
+/// A frame synchronizer attached to a [NDIReceiver]
+///
+/// Once the frame synchronizer is created, video and audio must no longer be received through
+/// [NDIReceiver::recv]. All other receiver functionality (metadata, tally, PTZ, ...) can still be
+/// used through [NDIFrameSync::receiver].
+///
+/// Captured frames hold a reference to the frame synchronizer, it is not destroyed until all of
+/// them are dropped or deallocated.
+///
+/// C equivalent: `NDIlib_framesync_instance_t`
+pub struct NDIFrameSync {
+    handle: Arc<RawFrameSync>,
+    receiver: NDIReceiver,
+}
+
+assert_impl_all!(NDIFrameSync: Send, Sync);
+
+impl NDIFrameSync {
+    /// Creates a frame synchronizer for the receiver, returns `None` if the SDK fails to create it.
+    pub fn new(receiver: NDIReceiver) -> Option<Self> {
+        let handle = unsafe { bindings::NDIlib_framesync_create(receiver.handle.raw_ptr()) };
+
+        NonNull::new(handle).map(|handle| NDIFrameSync {
+            handle: Arc::new(RawFrameSync {
+                handle,
+                _recv: receiver.handle.clone(),
+            }),
+            receiver,
+        })
+    }
+
+    /// The receiver the frame synchronizer pulls from
+    pub fn receiver(&self) -> &NDIReceiver {
+        &self.receiver
+    }
+
+    /// Captures the current video frame, repeating or dropping frames as necessary.
+    ///
+    /// Returns `false` if no video has been received yet, the frame is left unallocated in this case.
+    /// The frame must not be allocated before.
+    pub fn capture_video(&self, frame: &mut VideoFrame, field_mode: NDIFieldedFrameMode) -> bool {
+        let ptr = frame.to_ffi_recv_frame_ptr();
+        if ptr.is_null() {
+            return false;
+        }
+
+        unsafe {
+            bindings::NDIlib_framesync_capture_video(
+                self.handle.raw_ptr(),
+                ptr,
+                field_mode.to_ffi(),
+            )
+        };
+
+        frame.alloc.update_from_framesync(self.handle.clone());
+
+        if frame.raw.p_data.is_null() {
+            // The SDK expects every captured frame to be freed, even if it is empty
+            frame.dealloc();
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Captures `samples` samples of audio, resampled to match the rate at which this is called.
+    ///
+    /// Use `None` for `sample_rate`/`channels` to use the format of the incoming audio.
+    /// If the requested format does not match the incoming audio, or no audio is available, silence is inserted.
+    ///
+    /// Returns `false` if no audio has been received yet and the format was not specified, the
+    /// frame is left unallocated in this case. The frame must not be allocated before.
+    pub fn capture_audio(
+        &self,
+        frame: &mut AudioFrame,
+        sample_rate: Option<u32>,
+        channels: Option<usize>,
+        samples: usize,
+    ) -> bool {
+        let ptr = frame.to_ffi_recv_frame_ptr();
+        if ptr.is_null() {
+            return false;
+        }
+
+        let sample_rate = sample_rate.map_or(0, |rate| {
+            rate.try_into().expect("Sample rate exceeds i32::MAX")
+        });
+        let channels = channels.map_or(0, |channels| {
+            channels.try_into().expect("Channel count exceeds i32::MAX")
+        });
+        let samples = samples.try_into().expect("Sample count exceeds i32::MAX");
+
+        unsafe {
+            bindings::NDIlib_framesync_capture_audio_v2(
+                self.handle.raw_ptr(),
+                ptr,
+                sample_rate,
+                channels,
+                samples,
+            )
+        };
+
+        frame.alloc.update_from_framesync(self.handle.clone());
+
+        if frame.raw.p_data.is_null() {
+            // The SDK expects every captured frame to be freed, even if it is empty
+            frame.dealloc();
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Returns the number of audio samples currently queued, this can be used to choose the
+    /// number of samples to capture when there is no fixed output clock.
+    pub fn audio_queue_depth(&self) -> usize {
+        let depth = unsafe { bindings::NDIlib_framesync_audio_queue_depth(self.handle.raw_ptr()) };
+        depth
+            .try_into()
+            .expect("[Fatal FFI Error] NDI SDK returned a invalid audio queue depth")
+    }
+}
+
+impl Debug for NDIFrameSync {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NDIFrameSync")
+            .field("handle", &self.handle)
+            .field("receiver", &self.receiver.handle)
+            .finish()
+    }
+}
@@ -38,19 +38,30 @@
 
 mod bindings;
 
+pub mod advertiser;
 pub mod blocking_update;
 pub mod buffer_info;
+pub mod convert;
+pub mod discovery;
 pub mod enums;
 pub mod find;
 pub mod four_cc;
 pub mod frame;
+pub mod framesync;
+pub mod listener;
+pub mod metadata;
+pub mod ptz;
 pub mod receiver;
+pub mod recording;
 pub mod resolution;
 pub mod router;
 pub mod sdk;
 pub mod sender;
 pub mod source;
+pub mod stats;
 pub mod subsampling;
 pub mod tally;
 pub mod timecode;
 pub mod util;
+pub mod v210;
+pub mod video_format;
@@ -0,0 +1,311 @@
+//! NDI receiver listener
+//!
+//! The counterpart to [crate::advertiser]: lists all receivers that are advertised to a discovery
+//! server, like [crate::find] lists all sources.
+//!
+//! <https://docs.ndi.video/all/developing-with-ndi/sdk/ndi-recv/receiver-discovery-and-monitoring>
+
+use core::slice;
+use std::{
+    ffi::{CStr, CString, NulError},
+    marker::PhantomData,
+    time::Duration,
+};
+
+use crate::{
+    bindings,
+    blocking_update::BlockingUpdate,
+    enums::{NDIReceiverCommand, NDIReceiverStreamType},
+    util::duration_to_ms,
+};
+
+/// Builder for [NDIRecvListener]
+#[non_exhaustive]
+#[derive(Debug, Clone, Default)]
+pub struct NDIRecvListenerBuilder {
+    url_address: Option<CString>,
+}
+
+impl NDIRecvListenerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the URL of the discovery server, if not set the configured discovery server is used.
+    pub fn url_address(mut self, url_address: &str) -> Result<Self, NulError> {
+        self.url_address = Some(CString::new(url_address)?);
+        Ok(self)
+    }
+
+    pub fn build(self) -> Option<NDIRecvListener> {
+        let options = bindings::NDIlib_recv_listener_create_t {
+            p_url_address: self
+                .url_address
+                .as_ref()
+                .map(|url| url.as_ptr())
+                .unwrap_or(std::ptr::null()),
+        };
+        let handle = unsafe { bindings::NDIlib_recv_listener_create(&options) };
+        if handle.is_null() {
+            None
+        } else {
+            Some(NDIRecvListener { handle })
+        }
+    }
+}
+
+/// NDI receiver listener
+///
+/// For more information see module docs
+///
+/// C equivalent: `NDIlib_recv_listener_instance_t`
+#[derive(Debug)]
+pub struct NDIRecvListener {
+    handle: bindings::NDIlib_recv_listener_instance_t,
+}
+unsafe impl Send for NDIRecvListener {}
+
+impl<'a> NDIRecvListener {
+    /// Checks if the listener is connected to the discovery server
+    pub fn is_connected(&self) -> bool {
+        unsafe { bindings::NDIlib_recv_listener_is_connected(self.handle) }
+    }
+
+    /// The URL of the discovery server the listener is connected to
+    pub fn get_server_url(&self) -> Option<&CStr> {
+        // SDK Docs: The string is owned by the listener instance and valid until it is destroyed
+        let url = unsafe { bindings::NDIlib_recv_listener_get_server_url(self.handle) };
+        if url.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(url) })
+        }
+    }
+
+    /// Returns an iterator over the receivers currently known to the discovery server[^note]
+    ///
+    /// [^note]: This needs an `&mut` as calling `NDIlib_recv_listener_get_receivers` invalidates all previous iterators
+    pub fn get_receiver_iter(&'a mut self) -> Option<impl Iterator<Item = NDIReceiverRef<'a>>> {
+        let mut num_receivers = 0u32;
+
+        // The returned list is owned by the listener and retained until the next call
+        let receivers = unsafe {
+            bindings::NDIlib_recv_listener_get_receivers(self.handle, &mut num_receivers)
+        };
+
+        if receivers.is_null() {
+            return None;
+        }
+
+        let slice = unsafe { slice::from_raw_parts(receivers, num_receivers as usize) };
+
+        Some(
+            slice
+                .iter()
+                .map(|receiver| unsafe { NDIReceiverRef::from(*receiver) }),
+        )
+    }
+
+    /// Blocks until the receiver list changes or the timeout is reached
+    pub fn wait_for_change(&mut self, timeout: Duration) -> BlockingUpdate<()> {
+        let changed = unsafe {
+            bindings::NDIlib_recv_listener_wait_for_receivers(self.handle, duration_to_ms(timeout))
+        };
+
+        BlockingUpdate::new((), changed)
+    }
+}
+
+impl Drop for NDIRecvListener {
+    fn drop(&mut self) {
+        unsafe { bindings::NDIlib_recv_listener_destroy(self.handle) }
+    }
+}
+
+/// A short-lived reference to a remote receiver descriptor
+///
+/// C equivalent: `NDIlib_receiver_t`
+#[derive(Clone)]
+pub struct NDIReceiverRef<'a> {
+    raw: bindings::NDIlib_receiver_t,
+    raw_ptrs: PhantomData<&'a CStr>,
+}
+
+unsafe fn opt_cstr<'a>(ptr: *const std::os::raw::c_char) -> Option<&'a CStr> {
+    if ptr.is_null() {
+        None
+    } else {
+        Some(unsafe { CStr::from_ptr(ptr) })
+    }
+}
+
+impl<'a> NDIReceiverRef<'a> {
+    pub(crate) unsafe fn from(receiver_t: bindings::NDIlib_receiver_t) -> Self {
+        NDIReceiverRef {
+            raw: receiver_t,
+            raw_ptrs: PhantomData,
+        }
+    }
+
+    /// Unique identifier of the receiver
+    pub fn uuid(&self) -> Option<&'a CStr> {
+        unsafe { opt_cstr(self.raw.p_uuid) }
+    }
+
+    /// Name of the receiver
+    pub fn name(&self) -> Option<&'a CStr> {
+        unsafe { opt_cstr(self.raw.p_name) }
+    }
+
+    /// Unique identifier of the input group
+    pub fn input_uuid(&self) -> Option<&'a CStr> {
+        unsafe { opt_cstr(self.raw.p_input_uuid) }
+    }
+
+    /// Name of the input group
+    pub fn input_name(&self) -> Option<&'a CStr> {
+        unsafe { opt_cstr(self.raw.p_input_name) }
+    }
+
+    /// Network address of the receiver
+    pub fn address(&self) -> Option<&'a CStr> {
+        unsafe { opt_cstr(self.raw.p_address) }
+    }
+
+    /// The stream types the receiver is receiving, unknown types are skipped
+    pub fn streams(&self) -> impl Iterator<Item = NDIReceiverStreamType> + use<'a> {
+        let streams: &'a [bindings::NDIlib_receiver_type_e] =
+            unsafe { raw_slice(self.raw.p_streams, self.raw.num_streams) };
+        streams
+            .iter()
+            .filter_map(|stream| NDIReceiverStreamType::from_ffi(*stream))
+    }
+
+    /// The commands the receiver accepts, unknown commands are skipped
+    pub fn commands(&self) -> impl Iterator<Item = NDIReceiverCommand> + use<'a> {
+        let commands: &'a [bindings::NDIlib_receiver_command_e] =
+            unsafe { raw_slice(self.raw.p_commands, self.raw.num_commands) };
+        commands
+            .iter()
+            .filter_map(|command| NDIReceiverCommand::from_ffi(*command))
+    }
+
+    /// Checks if the listener is subscribed to the events of this receiver
+    pub fn events_subscribed(&self) -> bool {
+        self.raw.events_subscribed
+    }
+
+    /// Converts it to an owned [NDIReceiverInfo] by cloning all information
+    pub fn to_owned(&self) -> NDIReceiverInfo {
+        let string = |s: Option<&CStr>| s.map(|s| s.to_string_lossy().into_owned());
+        NDIReceiverInfo {
+            uuid: string(self.uuid()),
+            name: string(self.name()),
+            input_uuid: string(self.input_uuid()),
+            input_name: string(self.input_name()),
+            address: string(self.address()),
+            streams: self.streams().collect(),
+            commands: self.commands().collect(),
+            events_subscribed: self.events_subscribed(),
+        }
+    }
+}
+
+unsafe fn raw_slice<'a, T>(ptr: *const T, len: u32) -> &'a [T] {
+    if ptr.is_null() {
+        &[]
+    } else {
+        unsafe { slice::from_raw_parts(ptr, len as usize) }
+    }
+}
+
+impl std::fmt::Debug for NDIReceiverRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NDIReceiverRef")
+            .field("uuid", &self.uuid())
+            .field("name", &self.name())
+            .field("input_name", &self.input_name())
+            .field("address", &self.address())
+            .field("streams", &self.streams().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// An owned remote receiver descriptor, see [NDIReceiverRef]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NDIReceiverInfo {
+    pub uuid: Option<String>,
+    pub name: Option<String>,
+    pub input_uuid: Option<String>,
+    pub input_name: Option<String>,
+    pub address: Option<String>,
+    pub streams: Vec<NDIReceiverStreamType>,
+    pub commands: Vec<NDIReceiverCommand>,
+    pub events_subscribed: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_receiver_ref() {
+        let name = c"Studio Monitor";
+        let address = c"192.168.0.10";
+        let mut streams = [
+            bindings::NDIlib_receiver_type_e_NDIlib_receiver_type_video,
+            bindings::NDIlib_receiver_type_e_NDIlib_receiver_type_none,
+            bindings::NDIlib_receiver_type_e_NDIlib_receiver_type_audio,
+        ];
+        let mut commands = [bindings::NDIlib_receiver_command_e_NDIlib_receiver_command_connect];
+
+        let receiver = unsafe {
+            NDIReceiverRef::from(bindings::NDIlib_receiver_t {
+                p_uuid: std::ptr::null(),
+                p_name: name.as_ptr(),
+                p_input_uuid: std::ptr::null(),
+                p_input_name: std::ptr::null(),
+                p_address: address.as_ptr(),
+                p_streams: streams.as_mut_ptr(),
+                num_streams: streams.len() as u32,
+                p_commands: commands.as_mut_ptr(),
+                num_commands: commands.len() as u32,
+                events_subscribed: true,
+            })
+        };
+
+        assert_eq!(receiver.name(), Some(name));
+        assert_eq!(receiver.uuid(), None);
+        assert_eq!(
+            receiver.streams().collect::<Vec<_>>(),
+            [NDIReceiverStreamType::Video, NDIReceiverStreamType::Audio]
+        );
+
+        let info = receiver.to_owned();
+        assert_eq!(info.name.as_deref(), Some("Studio Monitor"));
+        assert_eq!(info.address.as_deref(), Some("192.168.0.10"));
+        assert_eq!(info.commands, [NDIReceiverCommand::Connect]);
+        assert!(info.events_subscribed);
+    }
+
+    #[test]
+    fn test_empty_lists() {
+        let receiver = unsafe {
+            NDIReceiverRef::from(bindings::NDIlib_receiver_t {
+                p_uuid: std::ptr::null(),
+                p_name: std::ptr::null(),
+                p_input_uuid: std::ptr::null(),
+                p_input_name: std::ptr::null(),
+                p_address: std::ptr::null(),
+                p_streams: std::ptr::null_mut(),
+                num_streams: 3,
+                p_commands: std::ptr::null_mut(),
+                num_commands: 0,
+                events_subscribed: false,
+            })
+        };
+        assert_eq!(receiver.streams().count(), 0);
+        assert_eq!(receiver.to_owned().name, None);
+    }
+}
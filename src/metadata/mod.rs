@@ -0,0 +1,413 @@
+//! NDI metadata messages
+//!
+//! Metadata frames carry XML. This module provides a small XML model ([xml]) and typed versions of
+//! the messages defined by the NDI SDK, everything else is kept as [NDIMetadataMessage::Unknown].
+//!
+//! ```rust
+//! # use ndi_sdk_sys::{metadata::NDIMetadataMessage, tally::Tally};
+//! let frame = NDIMetadataMessage::TallyEcho(Tally::new(true, false)).to_frame().unwrap();
+//! assert_eq!(
+//!     frame.decode().unwrap(),
+//!     [NDIMetadataMessage::TallyEcho(Tally::new(true, false))]
+//! );
+//! ```
+//!
+//! <https://docs.ndi.video/all/developing-with-ndi/sdk/ndi-send#metadata>
+
+pub mod xml;
+
+use std::{
+    error::Error,
+    ffi::{CString, NulError},
+    fmt::Display,
+};
+
+use crate::{frame::metadata::MetadataFrame, tally::Tally};
+
+use xml::{XmlElement, XmlParseError, parse_fragment};
+
+/// A known NDI metadata message
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub enum NDIMetadataMessage {
+    /// `<ndi_tally_echo>`: the tally state a sender reports back to its receivers
+    TallyEcho(Tally),
+    /// `<ndi_capabilities>`: features supported by a source, sent as connection metadata
+    Capabilities(NDICapabilities),
+    /// `<ndi_product>`: product information, sent as connection metadata
+    Product(NDIProduct),
+    /// `<ndi_hwaccel>`: enables or disables hardware acceleration on the receiving side
+    HardwareAcceleration(bool),
+    /// `<ntk_ptz_...>`: PTZ commands, see [crate::ptz] for the receiver API
+    PTZ(XmlElement),
+    /// Any other element
+    Unknown(XmlElement),
+}
+
+impl NDIMetadataMessage {
+    /// Decodes a single element, unknown or malformed messages become [NDIMetadataMessage::Unknown]
+    pub fn from_element(element: XmlElement) -> Self {
+        let decoded = match element.name.as_str() {
+            "ndi_tally_echo" => Some(Self::TallyEcho(Tally::new(
+                bool_attribute(&element, "on_program").unwrap_or(false),
+                bool_attribute(&element, "on_preview").unwrap_or(false),
+            ))),
+            "ndi_capabilities" => Some(Self::Capabilities(NDICapabilities::from_element(&element))),
+            "ndi_product" => Some(Self::Product(NDIProduct::from_element(&element))),
+            "ndi_hwaccel" => bool_attribute(&element, "enabled").map(Self::HardwareAcceleration),
+            name if name.starts_with("ntk_ptz_") => return Self::PTZ(element),
+            _ => None,
+        };
+        decoded.unwrap_or(Self::Unknown(element))
+    }
+
+    pub fn to_element(&self) -> XmlElement {
+        match self {
+            Self::TallyEcho(tally) => XmlElement::new("ndi_tally_echo")
+                .attr("on_program", tally.program)
+                .attr("on_preview", tally.preview),
+            Self::Capabilities(capabilities) => capabilities.to_element(),
+            Self::Product(product) => product.to_element(),
+            Self::HardwareAcceleration(enabled) => {
+                XmlElement::new("ndi_hwaccel").attr("enabled", enabled)
+            }
+            Self::PTZ(element) | Self::Unknown(element) => element.clone(),
+        }
+    }
+
+    /// Creates a metadata frame containing this message, fails if it contains null characters
+    pub fn to_frame(&self) -> Result<MetadataFrame, NulError> {
+        MetadataFrame::from_element(&self.to_element())
+    }
+}
+
+impl From<XmlElement> for NDIMetadataMessage {
+    fn from(element: XmlElement) -> Self {
+        Self::from_element(element)
+    }
+}
+
+impl Display for NDIMetadataMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_element())
+    }
+}
+
+/// Content of `<ndi_capabilities>`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NDICapabilities {
+    /// URL of the web interface of the device (`web_control`)
+    pub web_control: Option<String>,
+    pub ptz: bool,
+    pub pan_tilt: bool,
+    pub zoom: bool,
+    pub iris: bool,
+    pub white_balance: bool,
+    pub exposure: bool,
+    pub record: bool,
+    /// Attributes not covered by the fields above
+    pub other: Vec<(String, String)>,
+}
+
+impl NDICapabilities {
+    const FLAGS: [&str; 7] = [
+        "ntk_ptz",
+        "ntk_pan_tilt",
+        "ntk_zoom",
+        "ntk_iris",
+        "ntk_white_balance",
+        "ntk_exposure",
+        "ntk_record",
+    ];
+
+    fn flags(&self) -> [bool; 7] {
+        [
+            self.ptz,
+            self.pan_tilt,
+            self.zoom,
+            self.iris,
+            self.white_balance,
+            self.exposure,
+            self.record,
+        ]
+    }
+
+    fn from_element(element: &XmlElement) -> Self {
+        let flag = |name| bool_attribute(element, name).unwrap_or(false);
+        NDICapabilities {
+            web_control: element.attribute("web_control").map(str::to_owned),
+            ptz: flag("ntk_ptz"),
+            pan_tilt: flag("ntk_pan_tilt"),
+            zoom: flag("ntk_zoom"),
+            iris: flag("ntk_iris"),
+            white_balance: flag("ntk_white_balance"),
+            exposure: flag("ntk_exposure"),
+            record: flag("ntk_record"),
+            other: element
+                .attributes
+                .iter()
+                .filter(|(name, _)| name != "web_control" && !Self::FLAGS.contains(&name.as_str()))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    fn to_element(&self) -> XmlElement {
+        let mut element = XmlElement::new("ndi_capabilities");
+        if let Some(web_control) = &self.web_control {
+            element.set_attribute("web_control", web_control);
+        }
+        for (name, set) in Self::FLAGS.into_iter().zip(self.flags()) {
+            if set {
+                element.set_attribute(name, true);
+            }
+        }
+        for (name, value) in &self.other {
+            element.set_attribute(name.as_str(), value);
+        }
+        element
+    }
+}
+
+/// Content of `<ndi_product>`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NDIProduct {
+    pub long_name: Option<String>,
+    pub short_name: Option<String>,
+    pub manufacturer: Option<String>,
+    pub version: Option<String>,
+    pub model_name: Option<String>,
+    pub serial: Option<String>,
+    pub session: Option<String>,
+}
+
+impl NDIProduct {
+    fn fields(&self) -> [(&'static str, &Option<String>); 7] {
+        [
+            ("long_name", &self.long_name),
+            ("short_name", &self.short_name),
+            ("manufacturer", &self.manufacturer),
+            ("version", &self.version),
+            ("model_name", &self.model_name),
+            ("serial", &self.serial),
+            ("session", &self.session),
+        ]
+    }
+
+    fn from_element(element: &XmlElement) -> Self {
+        let attribute = |name| element.attribute(name).map(str::to_owned);
+        NDIProduct {
+            long_name: attribute("long_name"),
+            short_name: attribute("short_name"),
+            manufacturer: attribute("manufacturer"),
+            version: attribute("version"),
+            model_name: attribute("model_name"),
+            serial: attribute("serial"),
+            session: attribute("session"),
+        }
+    }
+
+    fn to_element(&self) -> XmlElement {
+        let mut element = XmlElement::new("ndi_product");
+        for (name, value) in self.fields() {
+            if let Some(value) = value {
+                element.set_attribute(name, value);
+            }
+        }
+        element
+    }
+}
+
+fn bool_attribute(element: &XmlElement, name: &str) -> Option<bool> {
+    match element.attribute(name)?.trim() {
+        v if v.eq_ignore_ascii_case("true") || v == "1" => Some(true),
+        v if v.eq_ignore_ascii_case("false") || v == "0" => Some(false),
+        _ => None,
+    }
+}
+
+// XML
+impl MetadataFrame {
+    /// Creates a metadata frame from a serialized XML element, fails if it contains null characters
+    pub fn from_element(element: &XmlElement) -> Result<Self, NulError> {
+        Ok(Self::from_string(CString::new(element.to_string())?))
+    }
+
+    /// Replaces the content of the frame with a serialized XML element, the timecode is kept
+    ///
+    /// Fails if the element contains null characters, the frame is unchanged in this case.
+    pub fn set_element(&mut self, element: &XmlElement) -> Result<(), NulError> {
+        self.set_data(CString::new(element.to_string())?);
+        Ok(())
+    }
+
+    /// Creates a metadata frame containing all messages, fails if they contain null characters
+    pub fn from_messages<'a>(
+        messages: impl IntoIterator<Item = &'a NDIMetadataMessage>,
+    ) -> Result<Self, NulError> {
+        let xml: String = messages
+            .into_iter()
+            .map(|message| message.to_string())
+            .collect();
+        Ok(Self::from_string(CString::new(xml)?))
+    }
+
+    /// Parses the XML content of the frame, a frame may contain multiple top-level elements
+    pub fn parse_xml(&self) -> Result<Vec<XmlElement>, MetadataDecodeError> {
+        let data = self.to_str().ok_or(MetadataDecodeError::Empty)?;
+        let xml = data
+            .to_str()
+            .map_err(|_| MetadataDecodeError::InvalidUtf8)?;
+        parse_fragment(xml).map_err(MetadataDecodeError::Xml)
+    }
+
+    /// Parses the frame and decodes all messages
+    pub fn decode(&self) -> Result<Vec<NDIMetadataMessage>, MetadataDecodeError> {
+        Ok(self
+            .parse_xml()?
+            .into_iter()
+            .map(NDIMetadataMessage::from_element)
+            .collect())
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetadataDecodeError {
+    /// The frame contains no data
+    Empty,
+    InvalidUtf8,
+    Xml(XmlParseError),
+}
+
+impl Display for MetadataDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => f.write_str("Metadata frame is empty"),
+            Self::InvalidUtf8 => f.write_str("Metadata frame is not valid UTF-8"),
+            Self::Xml(err) => write!(f, "Invalid metadata XML: {err}"),
+        }
+    }
+}
+
+impl Error for MetadataDecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Xml(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(xml: &str) -> Vec<NDIMetadataMessage> {
+        MetadataFrame::from_string(CString::new(xml).unwrap())
+            .decode()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_decode_known() {
+        let messages = decode(
+            r#"<ndi_capabilities web_control="http://10.0.0.5/" ntk_ptz="true" ntk_zoom="false" vendor_x="1"/>
+               <ndi_product long_name="Camera &amp; Co" manufacturer="ACME" serial="42"/>
+               <ndi_tally_echo on_program="true" on_preview="false"/>
+               <ndi_hwaccel enabled="1"/>
+               <ntk_ptz_zoom zoom="0.5"/>"#,
+        );
+
+        let NDIMetadataMessage::Capabilities(capabilities) = &messages[0] else {
+            panic!("{messages:?}");
+        };
+        assert_eq!(
+            capabilities.web_control.as_deref(),
+            Some("http://10.0.0.5/")
+        );
+        assert!(capabilities.ptz && !capabilities.zoom);
+        assert_eq!(
+            capabilities.other,
+            [("vendor_x".to_owned(), "1".to_owned())]
+        );
+
+        let NDIMetadataMessage::Product(product) = &messages[1] else {
+            panic!("{messages:?}");
+        };
+        assert_eq!(product.long_name.as_deref(), Some("Camera & Co"));
+        assert_eq!(product.serial.as_deref(), Some("42"));
+        assert_eq!(product.version, None);
+
+        assert_eq!(
+            messages[2..4],
+            [
+                NDIMetadataMessage::TallyEcho(Tally::new(true, false)),
+                NDIMetadataMessage::HardwareAcceleration(true),
+            ]
+        );
+        assert!(
+            matches!(&messages[4], NDIMetadataMessage::PTZ(e) if e.attribute("zoom") == Some("0.5"))
+        );
+    }
+
+    #[test]
+    fn test_unknown_fallback() {
+        let messages = decode(r#"<custom a="b"><x/></custom><ndi_hwaccel enabled="maybe"/>"#);
+        assert!(matches!(&messages[0], NDIMetadataMessage::Unknown(e) if e.name == "custom"));
+        assert!(matches!(&messages[1], NDIMetadataMessage::Unknown(e) if e.name == "ndi_hwaccel"));
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let messages = [
+            NDIMetadataMessage::Capabilities(NDICapabilities {
+                web_control: Some("http://cam/?a=1&b=\"2\"".to_owned()),
+                pan_tilt: true,
+                record: true,
+                ..Default::default()
+            }),
+            NDIMetadataMessage::Product(NDIProduct {
+                short_name: Some("<Cam>".to_owned()),
+                ..Default::default()
+            }),
+            NDIMetadataMessage::TallyEcho(Tally::new(false, true)),
+        ];
+        let frame = MetadataFrame::from_messages(&messages).unwrap();
+        assert_eq!(frame.decode().unwrap(), messages);
+    }
+
+    #[test]
+    fn test_null_characters() {
+        let element = XmlElement::new("a").text("x\0y");
+        assert!(MetadataFrame::from_element(&element).is_err());
+        assert!(
+            NDIMetadataMessage::Unknown(element.clone())
+                .to_frame()
+                .is_err()
+        );
+
+        let attribute = XmlElement::new("a").attr("b", "\0");
+        assert!(MetadataFrame::from_messages(&[NDIMetadataMessage::Unknown(attribute)]).is_err());
+
+        let mut frame = MetadataFrame::from_element(&XmlElement::new("ok")).unwrap();
+        assert!(frame.set_element(&element).is_err());
+        assert_eq!(frame.to_str(), Some(c"<ok/>"));
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(
+            MetadataFrame::new().decode(),
+            Err(MetadataDecodeError::Empty)
+        );
+        assert_eq!(
+            MetadataFrame::from_string(CString::new("<a>").unwrap()).decode(),
+            Err(MetadataDecodeError::Xml(XmlParseError::UnexpectedEnd))
+        );
+        assert_eq!(
+            MetadataFrame::from_string(CString::new(vec![b'<', 0xff, b'>']).unwrap()).decode(),
+            Err(MetadataDecodeError::InvalidUtf8)
+        );
+    }
+}
@@ -0,0 +1,593 @@
+//! Minimal XML element model, parser and serializer
+//!
+//! NDI metadata only uses a small subset of XML: elements, attributes and text. Processing
+//! instructions (`<?xml ...?>`) and comments are skipped, CDATA sections are read as text.
+//! Namespaces and DTDs are not supported.
+
+use std::{error::Error, fmt::Display};
+
+/// An XML element with attributes and children
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct XmlElement {
+    pub name: String,
+    /// Attributes in document order
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<XmlNode>,
+}
+
+/// Child node of a [XmlElement]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum XmlNode {
+    Element(XmlElement),
+    Text(String),
+}
+
+impl XmlElement {
+    /// Creates an empty element
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name is not a valid XML name
+    pub fn new(name: impl Into<String>) -> Self {
+        let name = name.into();
+        assert!(is_valid_name(&name), "Invalid XML element name: {name:?}");
+        XmlElement {
+            name,
+            attributes: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Sets an attribute, replacing a previous value
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name is not a valid XML name
+    pub fn attr(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        self.set_attribute(name, value);
+        self
+    }
+
+    /// Appends a child element
+    pub fn child(mut self, child: XmlElement) -> Self {
+        self.children.push(XmlNode::Element(child));
+        self
+    }
+
+    /// Appends a text node
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.children.push(XmlNode::Text(text.into()));
+        self
+    }
+
+    /// Sets an attribute, replacing a previous value
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name is not a valid XML name
+    pub fn set_attribute(&mut self, name: impl Into<String>, value: impl ToString) {
+        let name = name.into();
+        assert!(is_valid_name(&name), "Invalid XML attribute name: {name:?}");
+        let value = value.to_string();
+        match self.attributes.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, v)) => *v = value,
+            None => self.attributes.push((name, value)),
+        }
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Iterates over the child elements, skipping text
+    pub fn elements(&self) -> impl Iterator<Item = &XmlElement> {
+        self.children.iter().filter_map(|child| match child {
+            XmlNode::Element(element) => Some(element),
+            XmlNode::Text(_) => None,
+        })
+    }
+
+    /// Returns the first child element with the given name
+    pub fn find(&self, name: &str) -> Option<&XmlElement> {
+        self.elements().find(|element| element.name == name)
+    }
+
+    /// Concatenates all text of this element and its descendants
+    pub fn text_content(&self) -> String {
+        let mut text = String::new();
+        self.collect_text(&mut text);
+        text
+    }
+
+    fn collect_text(&self, text: &mut String) {
+        for child in &self.children {
+            match child {
+                XmlNode::Element(element) => element.collect_text(text),
+                XmlNode::Text(t) => text.push_str(t),
+            }
+        }
+    }
+
+    /// Parses a document consisting of exactly one root element
+    pub fn parse(xml: &str) -> Result<Self, XmlParseError> {
+        let mut elements = parse_fragment(xml)?;
+        match elements.len() {
+            0 => Err(XmlParseError::NoRootElement),
+            1 => Ok(elements.remove(0)),
+            _ => Err(XmlParseError::MultipleRootElements),
+        }
+    }
+}
+
+impl Display for XmlElement {
+    /// Serializes the element, text and attribute values are escaped
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<{}", self.name)?;
+        for (name, value) in &self.attributes {
+            write!(f, " {name}=\"{}\"", Escape(value, true))?;
+        }
+        if self.children.is_empty() {
+            return f.write_str("/>");
+        }
+        f.write_str(">")?;
+        for child in &self.children {
+            match child {
+                XmlNode::Element(element) => write!(f, "{element}")?,
+                XmlNode::Text(text) => write!(f, "{}", Escape(text, false))?,
+            }
+        }
+        write!(f, "</{}>", self.name)
+    }
+}
+
+/// Escapes text (`attribute = false`) or attribute values (`attribute = true`)
+struct Escape<'a>(&'a str, bool);
+
+impl Display for Escape<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Escape(s, attribute) = *self;
+        let mut last = 0;
+        for (i, c) in s.char_indices() {
+            let escaped = match c {
+                '<' => "&lt;",
+                '>' => "&gt;",
+                '&' => "&amp;",
+                '"' if attribute => "&quot;",
+                '\n' if attribute => "&#10;",
+                '\t' if attribute => "&#9;",
+                _ => continue,
+            };
+            f.write_str(&s[last..i])?;
+            f.write_str(escaped)?;
+            last = i + c.len_utf8();
+        }
+        f.write_str(&s[last..])
+    }
+}
+
+fn is_name_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == ':'
+}
+
+fn is_name_char(c: char) -> bool {
+    is_name_start(c) || c.is_numeric() || c == '-' || c == '.'
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(is_name_start) && chars.all(is_name_char)
+}
+
+/// Parses a sequence of top-level elements, e.g. multiple metadata messages in one frame
+///
+/// Text between top-level elements must be whitespace.
+pub fn parse_fragment(xml: &str) -> Result<Vec<XmlElement>, XmlParseError> {
+    let mut parser = Parser { input: xml, pos: 0 };
+    let mut elements = Vec::new();
+
+    loop {
+        parser.skip_misc()?;
+        if parser.at_end() {
+            break;
+        }
+        if !parser.rest().starts_with('<') {
+            return Err(XmlParseError::UnexpectedChar {
+                position: parser.pos,
+                found: parser.peek().unwrap_or_default(),
+            });
+        }
+        elements.push(parser.parse_element()?);
+    }
+
+    Ok(elements)
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.input[self.pos..]
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.input.len()
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn unexpected(&self) -> XmlParseError {
+        match self.peek() {
+            Some(found) => XmlParseError::UnexpectedChar {
+                position: self.pos,
+                found,
+            },
+            None => XmlParseError::UnexpectedEnd,
+        }
+    }
+
+    fn expect(&mut self, s: &str) -> Result<(), XmlParseError> {
+        if self.rest().starts_with(s) {
+            self.pos += s.len();
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let trimmed = self.rest().trim_start();
+        self.pos = self.input.len() - trimmed.len();
+    }
+
+    /// Skips everything up to and including `end`
+    fn skip_past(&mut self, end: &str) -> Result<&str, XmlParseError> {
+        let rest = &self.input[self.pos..];
+        let idx = rest.find(end).ok_or(XmlParseError::UnexpectedEnd)?;
+        self.pos += idx + end.len();
+        Ok(&rest[..idx])
+    }
+
+    /// Skips whitespace, comments and processing instructions
+    fn skip_misc(&mut self) -> Result<(), XmlParseError> {
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if self.rest().starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn parse_name(&mut self) -> Result<String, XmlParseError> {
+        let start = self.pos;
+        match self.peek() {
+            Some(c) if is_name_start(c) => {}
+            _ => return Err(self.unexpected()),
+        }
+        let len = self
+            .rest()
+            .find(|c: char| !is_name_char(c))
+            .unwrap_or(self.rest().len());
+        self.pos += len;
+        Ok(self.input[start..self.pos].to_owned())
+    }
+
+    fn parse_element(&mut self) -> Result<XmlElement, XmlParseError> {
+        self.expect("<")?;
+        let name = self.parse_name()?;
+        let mut element = XmlElement {
+            name,
+            attributes: Vec::new(),
+            children: Vec::new(),
+        };
+
+        loop {
+            let had_whitespace = self.rest().starts_with(char::is_whitespace);
+            self.skip_whitespace();
+            if self.rest().starts_with("/>") {
+                self.pos += 2;
+                return Ok(element);
+            }
+            if self.rest().starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            if !had_whitespace {
+                return Err(self.unexpected());
+            }
+
+            let attr_pos = self.pos;
+            let attr_name = self.parse_name()?;
+            self.skip_whitespace();
+            self.expect("=")?;
+            self.skip_whitespace();
+            let quote = match self.peek() {
+                Some(q @ ('"' | '\'')) => q,
+                _ => return Err(self.unexpected()),
+            };
+            self.pos += 1;
+            let value_pos = self.pos;
+            let raw = self.skip_past(&quote.to_string())?;
+            if let Some(idx) = raw.find('<') {
+                return Err(XmlParseError::UnexpectedChar {
+                    position: value_pos + idx,
+                    found: '<',
+                });
+            }
+            let value = unescape(raw, value_pos)?;
+
+            if element.attribute(&attr_name).is_some() {
+                return Err(XmlParseError::DuplicateAttribute {
+                    position: attr_pos,
+                    name: attr_name,
+                });
+            }
+            element.attributes.push((attr_name, value));
+        }
+
+        // Content
+        let mut text = String::new();
+        loop {
+            if self.at_end() {
+                return Err(XmlParseError::UnexpectedEnd);
+            }
+            let rest = self.rest();
+            if rest.starts_with("</") {
+                self.pos += 2;
+                let close_pos = self.pos;
+                let close = self.parse_name()?;
+                self.skip_whitespace();
+                self.expect(">")?;
+                if close != element.name {
+                    return Err(XmlParseError::MismatchedTag {
+                        position: close_pos,
+                        expected: element.name,
+                        found: close,
+                    });
+                }
+                push_text(&mut element, &mut text);
+                return Ok(element);
+            } else if rest.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += "<![CDATA[".len();
+                text.push_str(self.skip_past("]]>")?);
+            } else if rest.starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if rest.starts_with('<') {
+                push_text(&mut element, &mut text);
+                let child = self.parse_element()?;
+                element.children.push(XmlNode::Element(child));
+            } else {
+                let len = rest.find('<').unwrap_or(rest.len());
+                let start = self.pos;
+                self.pos += len;
+                text.push_str(&unescape(&self.input[start..self.pos], start)?);
+            }
+        }
+    }
+}
+
+/// Adds the collected text as a child, whitespace between elements is dropped
+fn push_text(element: &mut XmlElement, text: &mut String) {
+    if !text.trim().is_empty() {
+        element.children.push(XmlNode::Text(std::mem::take(text)));
+    }
+    text.clear();
+}
+
+/// Resolves entity and character references, `offset` is the position of `s` in the input
+fn unescape(s: &str, offset: usize) -> Result<String, XmlParseError> {
+    if !s.contains('&') {
+        return Ok(s.to_owned());
+    }
+
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(idx) = rest.find('&') {
+        out.push_str(&rest[..idx]);
+        let position = offset + (s.len() - rest.len()) + idx;
+        let after = &rest[idx + 1..];
+        let end = after
+            .find(';')
+            .ok_or_else(|| XmlParseError::InvalidEntity {
+                position,
+                entity: after.chars().take(8).collect(),
+            })?;
+        let entity = &after[..end];
+        let c = match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => {
+                let code = if let Some(hex) = entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                {
+                    u32::from_str_radix(hex, 16).ok()
+                } else if let Some(dec) = entity.strip_prefix('#') {
+                    dec.parse().ok()
+                } else {
+                    None
+                };
+                code.and_then(char::from_u32).filter(|c| *c != '\0')
+            }
+        };
+        out.push(c.ok_or_else(|| XmlParseError::InvalidEntity {
+            position,
+            entity: entity.to_owned(),
+        })?);
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Positions are byte offsets into the input
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum XmlParseError {
+    UnexpectedEnd,
+    UnexpectedChar {
+        position: usize,
+        found: char,
+    },
+    MismatchedTag {
+        position: usize,
+        expected: String,
+        found: String,
+    },
+    DuplicateAttribute {
+        position: usize,
+        name: String,
+    },
+    InvalidEntity {
+        position: usize,
+        entity: String,
+    },
+    NoRootElement,
+    MultipleRootElements,
+}
+
+impl Display for XmlParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedEnd => f.write_str("Unexpected end of XML input"),
+            Self::UnexpectedChar { position, found } => {
+                write!(f, "Unexpected character {found:?} at {position}")
+            }
+            Self::MismatchedTag {
+                position,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Closing tag </{found}> at {position} does not match <{expected}>"
+            ),
+            Self::DuplicateAttribute { position, name } => {
+                write!(f, "Duplicate attribute {name:?} at {position}")
+            }
+            Self::InvalidEntity { position, entity } => {
+                write!(f, "Invalid entity reference &{entity}; at {position}")
+            }
+            Self::NoRootElement => f.write_str("XML document has no root element"),
+            Self::MultipleRootElements => f.write_str("XML document has multiple root elements"),
+        }
+    }
+}
+
+impl Error for XmlParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let xml = r#"<?xml version="1.0"?>
+            <!-- comment -->
+            <root a="1" b='two &amp; three'>
+                <child x="&lt;&#65;&#x42;&gt;"/>
+                text &amp; more<![CDATA[<raw>]]>
+                <child>inner</child>
+            </root>"#;
+        let root = XmlElement::parse(xml).unwrap();
+        assert_eq!(root.name, "root");
+        assert_eq!(root.attribute("a"), Some("1"));
+        assert_eq!(root.attribute("b"), Some("two & three"));
+        assert_eq!(root.elements().count(), 2);
+        assert_eq!(root.find("child").unwrap().attribute("x"), Some("<AB>"));
+        assert!(root.text_content().contains("text & more<raw>"));
+        assert!(root.text_content().ends_with("inner"));
+    }
+
+    #[test]
+    fn test_roundtrip_escaping() {
+        let element = XmlElement::new("ndi_test")
+            .attr("quote", "say \"hi\" & <bye>")
+            .attr("lines", "a\nb")
+            .child(XmlElement::new("inner").text("1 < 2 && 3 > 2"));
+        let xml = element.to_string();
+        assert_eq!(
+            xml,
+            "<ndi_test quote=\"say &quot;hi&quot; &amp; &lt;bye&gt;\" lines=\"a&#10;b\">\
+             <inner>1 &lt; 2 &amp;&amp; 3 &gt; 2</inner></ndi_test>"
+        );
+        assert_eq!(XmlElement::parse(&xml).unwrap(), element);
+    }
+
+    #[test]
+    fn test_fragment() {
+        let elements = parse_fragment("<a/> <b c=\"d\"></b>\n").unwrap();
+        assert_eq!(elements.len(), 2);
+        assert_eq!(elements[1].attribute("c"), Some("d"));
+        assert_eq!(parse_fragment("  ").unwrap(), []);
+        assert_eq!(
+            XmlElement::parse("<a/><b/>"),
+            Err(XmlParseError::MultipleRootElements)
+        );
+        assert_eq!(XmlElement::parse(""), Err(XmlParseError::NoRootElement));
+    }
+
+    #[test]
+    fn test_malformed() {
+        use XmlParseError::*;
+        assert_eq!(XmlElement::parse("<a>"), Err(UnexpectedEnd));
+        assert_eq!(XmlElement::parse("<a x=\"1/>"), Err(UnexpectedEnd));
+        assert_eq!(
+            XmlElement::parse("<a></b>"),
+            Err(MismatchedTag {
+                position: 5,
+                expected: "a".to_owned(),
+                found: "b".to_owned()
+            })
+        );
+        assert_eq!(
+            XmlElement::parse("<a x=\"1\" x=\"2\"/>"),
+            Err(DuplicateAttribute {
+                position: 9,
+                name: "x".to_owned()
+            })
+        );
+        assert_eq!(
+            XmlElement::parse("<a>&bogus;</a>"),
+            Err(InvalidEntity {
+                position: 3,
+                entity: "bogus".to_owned()
+            })
+        );
+        assert!(matches!(
+            XmlElement::parse("<a>&#0;</a>"),
+            Err(InvalidEntity { .. })
+        ));
+        assert!(matches!(
+            XmlElement::parse("<a x=\"1\"y=\"2\"/>"),
+            Err(UnexpectedChar { found: 'y', .. })
+        ));
+        assert!(matches!(
+            XmlElement::parse("<1a/>"),
+            Err(UnexpectedChar { found: '1', .. })
+        ));
+        assert!(matches!(
+            XmlElement::parse("text <a/>"),
+            Err(UnexpectedChar { found: 't', .. })
+        ));
+        assert!(matches!(
+            XmlElement::parse("<a x=\"<\"/>"),
+            Err(UnexpectedChar { found: '<', .. })
+        ));
+    }
+}
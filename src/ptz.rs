@@ -0,0 +1,349 @@
+//! PTZ (pan, tilt, zoom) camera control
+//!
+//! PTZ commands are sent over the connection of a [NDIReceiver]. All values are checked before
+//! they are passed to the SDK, the SDK itself silently ignores invalid values.
+//!
+//! <https://docs.ndi.video/all/developing-with-ndi/sdk/ndi-recv/receiver-ptz-control>
+
+use std::{error::Error, ops::RangeInclusive};
+
+use crate::{bindings, receiver::NDIReceiver};
+
+/// Highest preset index supported by the SDK
+pub const MAX_PRESET: u8 = 99;
+
+/// Velocity of a PTZ movement, validated to be within `-1..=1`
+///
+/// Continuous movements (zoom, pan/tilt, focus) use the full range of `-1..=1`, where the sign
+/// selects the direction and `0` stops the movement. Preset recall only supports `0..=1`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct PTZSpeed(f32);
+
+impl PTZSpeed {
+    /// Stops a continuous movement
+    pub const STOP: PTZSpeed = PTZSpeed(0.);
+    /// Maximum speed in positive direction (zoom out, pan left, tilt up, focus inwards)
+    pub const MAX: PTZSpeed = PTZSpeed(1.);
+    /// Maximum speed in negative direction (zoom in, pan right, tilt down, focus outwards)
+    pub const MIN: PTZSpeed = PTZSpeed(-1.);
+
+    /// Creates a speed in the range `-1..=1`
+    pub fn new(speed: f32) -> Result<Self, PTZError> {
+        check_range("speed", speed, -1.0..=1.0).map(PTZSpeed)
+    }
+
+    pub fn as_f32(self) -> f32 {
+        self.0
+    }
+}
+
+impl TryFrom<f32> for PTZSpeed {
+    type Error = PTZError;
+
+    fn try_from(speed: f32) -> Result<Self, Self::Error> {
+        Self::new(speed)
+    }
+}
+
+/// White balance mode
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PTZWhiteBalance {
+    Auto,
+    Indoor,
+    Outdoor,
+    /// Sets the white balance once based on the current image and keeps it
+    OneShot,
+    /// Manual red/blue levels (`0..=1`)
+    Manual {
+        red: f32,
+        blue: f32,
+    },
+}
+
+impl PTZWhiteBalance {
+    fn validate(self) -> Result<Self, PTZError> {
+        if let Self::Manual { red, blue } = self {
+            check_range("red", red, 0.0..=1.0)?;
+            check_range("blue", blue, 0.0..=1.0)?;
+        }
+        Ok(self)
+    }
+}
+
+/// Exposure mode
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PTZExposure {
+    Auto,
+    /// Manual exposure level from `0` (dark) to `1` (light)
+    Manual(f32),
+    /// Manual iris, gain and shutter speed (all `0..=1`)
+    Detailed {
+        iris: f32,
+        gain: f32,
+        shutter_speed: f32,
+    },
+}
+
+impl PTZExposure {
+    fn validate(self) -> Result<Self, PTZError> {
+        match self {
+            Self::Auto => {}
+            Self::Manual(level) => {
+                check_range("exposure", level, 0.0..=1.0)?;
+            }
+            Self::Detailed {
+                iris,
+                gain,
+                shutter_speed,
+            } => {
+                check_range("iris", iris, 0.0..=1.0)?;
+                check_range("gain", gain, 0.0..=1.0)?;
+                check_range("shutter_speed", shutter_speed, 0.0..=1.0)?;
+            }
+        }
+        Ok(self)
+    }
+}
+
+fn check_range(
+    name: &'static str,
+    value: f32,
+    range: RangeInclusive<f32>,
+) -> Result<f32, PTZError> {
+    if range.contains(&value) {
+        Ok(value)
+    } else {
+        Err(PTZError::OutOfRange {
+            name,
+            value,
+            min: *range.start(),
+            max: *range.end(),
+        })
+    }
+}
+
+fn check_preset(preset: u8) -> Result<i32, PTZError> {
+    if preset <= MAX_PRESET {
+        Ok(preset as i32)
+    } else {
+        Err(PTZError::InvalidPreset(preset))
+    }
+}
+
+fn check_sdk_result(success: bool) -> Result<(), PTZError> {
+    if success {
+        Ok(())
+    } else {
+        Err(PTZError::CommandFailed)
+    }
+}
+
+// PTZ control
+impl NDIReceiver {
+    /// Checks if the connected source supports PTZ control.
+    /// This is only known after the connection is established and the first frames were received.
+    pub fn ptz_is_supported(&self) -> bool {
+        unsafe { bindings::NDIlib_recv_ptz_is_supported(self.handle.raw_ptr()) }
+    }
+
+    /// Zooms to an absolute value from `0` (zoomed in) to `1` (zoomed out)
+    pub fn ptz_zoom(&self, zoom: f32) -> Result<(), PTZError> {
+        let zoom = check_range("zoom", zoom, 0.0..=1.0)?;
+        check_sdk_result(unsafe { bindings::NDIlib_recv_ptz_zoom(self.handle.raw_ptr(), zoom) })
+    }
+
+    /// Zooms continuously out (positive) or in (negative) until stopped
+    pub fn ptz_zoom_speed(&self, speed: PTZSpeed) -> Result<(), PTZError> {
+        check_sdk_result(unsafe {
+            bindings::NDIlib_recv_ptz_zoom_speed(self.handle.raw_ptr(), speed.0)
+        })
+    }
+
+    /// Moves to an absolute position, pan and tilt range from `-1` (left/down) to `1` (right/up)
+    pub fn ptz_pan_tilt(&self, pan: f32, tilt: f32) -> Result<(), PTZError> {
+        let pan = check_range("pan", pan, -1.0..=1.0)?;
+        let tilt = check_range("tilt", tilt, -1.0..=1.0)?;
+        check_sdk_result(unsafe {
+            bindings::NDIlib_recv_ptz_pan_tilt(self.handle.raw_ptr(), pan, tilt)
+        })
+    }
+
+    /// Pans and tilts continuously until stopped
+    ///
+    /// Positive speeds pan left and tilt up, negative speeds pan right and tilt down.
+    /// Note that this is the opposite pan direction of [NDIReceiver::ptz_pan_tilt].
+    pub fn ptz_pan_tilt_speed(&self, pan: PTZSpeed, tilt: PTZSpeed) -> Result<(), PTZError> {
+        check_sdk_result(unsafe {
+            bindings::NDIlib_recv_ptz_pan_tilt_speed(self.handle.raw_ptr(), pan.0, tilt.0)
+        })
+    }
+
+    /// Stores the current position as preset (`0..=99`)
+    pub fn ptz_store_preset(&self, preset: u8) -> Result<(), PTZError> {
+        let preset = check_preset(preset)?;
+        check_sdk_result(unsafe {
+            bindings::NDIlib_recv_ptz_store_preset(self.handle.raw_ptr(), preset)
+        })
+    }
+
+    /// Moves to a stored preset (`0..=99`), the speed must not be negative
+    pub fn ptz_recall_preset(&self, preset: u8, speed: PTZSpeed) -> Result<(), PTZError> {
+        let preset = check_preset(preset)?;
+        let speed = check_range("speed", speed.0, 0.0..=1.0)?;
+        check_sdk_result(unsafe {
+            bindings::NDIlib_recv_ptz_recall_preset(self.handle.raw_ptr(), preset, speed)
+        })
+    }
+
+    /// Enables auto focus
+    pub fn ptz_auto_focus(&self) -> Result<(), PTZError> {
+        check_sdk_result(unsafe { bindings::NDIlib_recv_ptz_auto_focus(self.handle.raw_ptr()) })
+    }
+
+    /// Sets the focus to an absolute value from `0` (infinity) to `1` (closest)
+    pub fn ptz_focus(&self, focus: f32) -> Result<(), PTZError> {
+        let focus = check_range("focus", focus, 0.0..=1.0)?;
+        check_sdk_result(unsafe { bindings::NDIlib_recv_ptz_focus(self.handle.raw_ptr(), focus) })
+    }
+
+    /// Changes the focus continuously inwards (positive) or outwards (negative) until stopped
+    pub fn ptz_focus_speed(&self, speed: PTZSpeed) -> Result<(), PTZError> {
+        check_sdk_result(unsafe {
+            bindings::NDIlib_recv_ptz_focus_speed(self.handle.raw_ptr(), speed.0)
+        })
+    }
+
+    pub fn ptz_white_balance(&self, mode: PTZWhiteBalance) -> Result<(), PTZError> {
+        let ptr = self.handle.raw_ptr();
+        check_sdk_result(match mode.validate()? {
+            PTZWhiteBalance::Auto => unsafe { bindings::NDIlib_recv_ptz_white_balance_auto(ptr) },
+            PTZWhiteBalance::Indoor => unsafe {
+                bindings::NDIlib_recv_ptz_white_balance_indoor(ptr)
+            },
+            PTZWhiteBalance::Outdoor => unsafe {
+                bindings::NDIlib_recv_ptz_white_balance_outdoor(ptr)
+            },
+            PTZWhiteBalance::OneShot => unsafe {
+                bindings::NDIlib_recv_ptz_white_balance_oneshot(ptr)
+            },
+            PTZWhiteBalance::Manual { red, blue } => unsafe {
+                bindings::NDIlib_recv_ptz_white_balance_manual(ptr, red, blue)
+            },
+        })
+    }
+
+    pub fn ptz_exposure(&self, mode: PTZExposure) -> Result<(), PTZError> {
+        let ptr = self.handle.raw_ptr();
+        check_sdk_result(match mode.validate()? {
+            PTZExposure::Auto => unsafe { bindings::NDIlib_recv_ptz_exposure_auto(ptr) },
+            PTZExposure::Manual(level) => unsafe {
+                bindings::NDIlib_recv_ptz_exposure_manual(ptr, level)
+            },
+            PTZExposure::Detailed {
+                iris,
+                gain,
+                shutter_speed,
+            } => unsafe {
+                bindings::NDIlib_recv_ptz_exposure_manual_v2(ptr, iris, gain, shutter_speed)
+            },
+        })
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PTZError {
+    /// A value is outside of the range supported by the SDK
+    OutOfRange {
+        name: &'static str,
+        value: f32,
+        min: f32,
+        max: f32,
+    },
+    /// The preset index is larger than [MAX_PRESET]
+    InvalidPreset(u8),
+    /// The SDK rejected the command, usually because the source is not connected or does not support PTZ
+    CommandFailed,
+}
+
+impl std::fmt::Display for PTZError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OutOfRange {
+                name,
+                value,
+                min,
+                max,
+            } => write!(f, "PTZ {name} {value} is out of range ({min}..={max})"),
+            Self::InvalidPreset(preset) => {
+                write!(f, "PTZ preset {preset} is out of range (0..={MAX_PRESET})")
+            }
+            Self::CommandFailed => f.write_str("PTZ command failed"),
+        }
+    }
+}
+
+impl Error for PTZError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_speed() {
+        assert_eq!(PTZSpeed::new(0.5).unwrap().as_f32(), 0.5);
+        assert_eq!(PTZSpeed::try_from(-1.).unwrap(), PTZSpeed::MIN);
+        assert_eq!(PTZSpeed::default(), PTZSpeed::STOP);
+        assert_eq!(
+            PTZSpeed::new(1.5),
+            Err(PTZError::OutOfRange {
+                name: "speed",
+                value: 1.5,
+                min: -1.,
+                max: 1.
+            })
+        );
+        assert!(PTZSpeed::new(f32::NAN).is_err());
+    }
+
+    #[test]
+    fn test_ranges() {
+        assert_eq!(check_range("zoom", 1., 0.0..=1.0), Ok(1.));
+        assert!(check_range("zoom", -0.1, 0.0..=1.0).is_err());
+        assert!(check_range("pan", f32::INFINITY, -1.0..=1.0).is_err());
+
+        assert_eq!(check_preset(99), Ok(99));
+        assert_eq!(check_preset(100), Err(PTZError::InvalidPreset(100)));
+    }
+
+    #[test]
+    fn test_modes() {
+        assert!(PTZWhiteBalance::Auto.validate().is_ok());
+        assert!(
+            PTZWhiteBalance::Manual { red: 0.2, blue: 1. }
+                .validate()
+                .is_ok()
+        );
+        assert!(matches!(
+            PTZWhiteBalance::Manual { red: 0.2, blue: 2. }.validate(),
+            Err(PTZError::OutOfRange { name: "blue", .. })
+        ));
+
+        assert!(PTZExposure::Manual(0.).validate().is_ok());
+        assert!(PTZExposure::Manual(-0.5).validate().is_err());
+        assert!(matches!(
+            PTZExposure::Detailed {
+                iris: 0.5,
+                gain: 0.5,
+                shutter_speed: 1.5
+            }
+            .validate(),
+            Err(PTZError::OutOfRange {
+                name: "shutter_speed",
+                ..
+            })
+        ));
+    }
+}
@@ -7,13 +7,14 @@ use std::{
     ffi::{CStr, CString},
     fmt::Debug,
     ptr::NonNull,
-    sync::Arc,
+    sync::{Arc, Mutex},
     time::Duration,
 };
 
 use static_assertions::assert_impl_all;
 
 use crate::{
+    advertiser::RawRecvAdvertiser,
     bindings::{self},
     enums::{NDIBandwidthMode, NDIPreferredColorFormat, NDIRecvError},
     frame::{
@@ -118,7 +119,10 @@ impl<Source: NDISourceLike> NDIReceiverBuilder<Source> {
 
             if let Some(handle) = NonNull::new(handle) {
                 Ok(NDIReceiver {
-                    handle: Arc::new(RawReceiver { handle }),
+                    handle: Arc::new(RawReceiver {
+                        handle,
+                        advertisers: Mutex::default(),
+                    }),
                 })
             } else {
                 Err(NDIReceiverBuilderError::CreationFailed)
@@ -143,9 +147,10 @@ impl std::fmt::Display for NDIReceiverBuilderError {
 
 impl Error for NDIReceiverBuilderError {}
 
-#[derive(PartialEq, Eq)]
 pub(crate) struct RawReceiver {
     handle: NonNull<bindings::NDIlib_recv_instance_type>,
+    /// Advertisers this receiver is registered with, it is removed from them before it is destroyed
+    pub(crate) advertisers: Mutex<Vec<Arc<RawRecvAdvertiser>>>,
 }
 
 impl RawReceiver {
@@ -154,8 +159,24 @@ impl RawReceiver {
     }
 }
 
+impl PartialEq for RawReceiver {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
+
+impl Eq for RawReceiver {}
+
 impl Drop for RawReceiver {
     fn drop(&mut self) {
+        let advertisers = match self.advertisers.get_mut() {
+            Ok(advertisers) => std::mem::take(advertisers),
+            Err(e) => std::mem::take(e.into_inner()),
+        };
+        for advertiser in advertisers {
+            unsafe { advertiser.del_receiver(self.raw_ptr()) };
+        }
+
         unsafe { bindings::NDIlib_recv_destroy(self.raw_ptr()) };
     }
 }
@@ -178,7 +199,7 @@ unsafe impl Sync for RawReceiver {}
 ///
 /// [^note]: The inner receiver is [Arc]ed because all frames received need to be dropped on the receiver handle and therefore need a valid reference to it
 pub struct NDIReceiver {
-    handle: Arc<RawReceiver>,
+    pub(crate) handle: Arc<RawReceiver>,
 }
 
 assert_impl_all!(NDIReceiver: Send, Sync);
@@ -352,7 +373,7 @@ impl NDIReceiver {
         }
     }
 
-    unsafe fn free_string(&self, ptr: *const std::os::raw::c_char) {
+    pub(crate) unsafe fn free_string(&self, ptr: *const std::os::raw::c_char) {
         if !ptr.is_null() {
             unsafe { bindings::NDIlib_recv_free_string(self.handle.raw_ptr(), ptr) };
         }
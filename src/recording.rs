@@ -0,0 +1,206 @@
+//! Receiver-side recording
+//!
+//! Some sources (e.g. NDI HX cameras) support recording the compressed stream on the receiving
+//! machine without decoding it.
+//!
+//! <https://docs.ndi.video/all/developing-with-ndi/sdk/ndi-recv/receiver-recording>
+
+use std::{
+    error::Error,
+    ffi::{CStr, CString},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{bindings, receiver::NDIReceiver, timecode::NDITime};
+
+/// Number of recorded frames and timing information of the current recording
+///
+/// C equivalent: `NDIlib_recv_recording_time_t`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RecordingTimes {
+    /// The number of recorded video frames
+    pub frames: u64,
+    /// Timecode of the first recorded frame
+    pub start: NDITime,
+    /// Timecode of the last recorded frame
+    pub last: NDITime,
+}
+
+impl RecordingTimes {
+    /// The duration between the first and the last recorded frame
+    pub fn duration(&self) -> Duration {
+        let ticks = self
+            .last
+            .to_ffi()
+            .saturating_sub(self.start.to_ffi())
+            .max(0) as u64;
+        Duration::from_nanos(ticks.saturating_mul(100))
+    }
+
+    pub(crate) fn from_ffi(times: &bindings::NDIlib_recv_recording_time_t) -> Self {
+        RecordingTimes {
+            frames: times.no_frames.max(0) as u64,
+            start: NDITime::from_ffi(times.start_time),
+            last: NDITime::from_ffi(times.last_time),
+        }
+    }
+}
+
+// Recording
+impl NDIReceiver {
+    /// Checks if the connected source supports recording.
+    pub fn recording_is_supported(&self) -> bool {
+        unsafe { bindings::NDIlib_recv_recording_is_supported(self.handle.raw_ptr()) }
+    }
+
+    /// Starts recording.
+    ///
+    /// The filename is only a hint, the SDK may append a suffix or extension. Use
+    /// [NDIReceiver::recording_filename] to get the actual path. With `None` a name is generated.
+    pub fn recording_start(&self, filename_hint: Option<&Path>) -> Result<(), RecordingError> {
+        let filename_hint = filename_hint
+            .map(|path| {
+                path.to_str()
+                    .and_then(|path| CString::new(path).ok())
+                    .ok_or_else(|| RecordingError::InvalidPath(path.to_path_buf()))
+            })
+            .transpose()?;
+
+        let ptr = filename_hint
+            .as_ref()
+            .map(|hint| hint.as_ptr())
+            .unwrap_or(std::ptr::null());
+
+        if unsafe { bindings::NDIlib_recv_recording_start(self.handle.raw_ptr(), ptr) } {
+            Ok(())
+        } else {
+            Err(self
+                .recording_error()
+                .unwrap_or(RecordingError::StartFailed))
+        }
+    }
+
+    /// Stops the recording.
+    pub fn recording_stop(&self) -> Result<(), RecordingError> {
+        if unsafe { bindings::NDIlib_recv_recording_stop(self.handle.raw_ptr()) } {
+            Ok(())
+        } else {
+            Err(RecordingError::StopFailed)
+        }
+    }
+
+    /// Sets the audio level of the recording in dB (`0` is unity gain).
+    pub fn recording_set_audio_level(&self, level_db: f32) -> Result<(), RecordingError> {
+        if !level_db.is_finite() {
+            Err(RecordingError::InvalidAudioLevel(level_db))?;
+        }
+
+        if unsafe {
+            bindings::NDIlib_recv_recording_set_audio_level(self.handle.raw_ptr(), level_db)
+        } {
+            Ok(())
+        } else {
+            Err(RecordingError::SetAudioLevelFailed)
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        unsafe { bindings::NDIlib_recv_recording_is_recording(self.handle.raw_ptr()) }
+    }
+
+    /// The path of the current recording
+    pub fn recording_filename(&self) -> Option<PathBuf> {
+        let ptr = unsafe { bindings::NDIlib_recv_recording_get_filename(self.handle.raw_ptr()) };
+        self.take_string(ptr).map(PathBuf::from)
+    }
+
+    /// The last error that occurred during recording (e.g. disk full)
+    pub fn recording_error(&self) -> Option<RecordingError> {
+        let ptr = unsafe { bindings::NDIlib_recv_recording_get_error(self.handle.raw_ptr()) };
+        self.take_string(ptr).map(RecordingError::Recorder)
+    }
+
+    /// Returns timing information of the current recording, `None` if nothing is recorded.
+    pub fn recording_times(&self) -> Option<RecordingTimes> {
+        let mut times = bindings::NDIlib_recv_recording_time_t {
+            no_frames: 0,
+            start_time: 0,
+            last_time: 0,
+        };
+
+        if unsafe { bindings::NDIlib_recv_recording_get_times(self.handle.raw_ptr(), &mut times) } {
+            Some(RecordingTimes::from_ffi(&times))
+        } else {
+            None
+        }
+    }
+
+    /// Copies and frees a string allocated by the SDK
+    fn take_string(&self, ptr: *const std::os::raw::c_char) -> Option<String> {
+        if ptr.is_null() {
+            None
+        } else {
+            let string = unsafe { CStr::from_ptr(ptr) }
+                .to_string_lossy()
+                .into_owned();
+            unsafe { self.free_string(ptr) };
+            Some(string)
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordingError {
+    /// The path is not valid UTF-8 or contains a null byte
+    InvalidPath(PathBuf),
+    /// The audio level is not a finite number
+    InvalidAudioLevel(f32),
+    /// The recording could not be started (e.g. the source does not support recording)
+    StartFailed,
+    /// The recording could not be stopped (e.g. nothing is recorded)
+    StopFailed,
+    SetAudioLevelFailed,
+    /// An error reported by the recorder
+    Recorder(String),
+}
+
+impl std::fmt::Display for RecordingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidPath(path) => write!(f, "Invalid recording path: {}", path.display()),
+            Self::InvalidAudioLevel(level) => write!(f, "Invalid recording audio level: {level}"),
+            Self::StartFailed => f.write_str("Starting the recording failed"),
+            Self::StopFailed => f.write_str("Stopping the recording failed"),
+            Self::SetAudioLevelFailed => f.write_str("Setting the recording audio level failed"),
+            Self::Recorder(message) => write!(f, "Recording error: {message}"),
+        }
+    }
+}
+
+impl Error for RecordingError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_times() {
+        let times = RecordingTimes::from_ffi(&bindings::NDIlib_recv_recording_time_t {
+            no_frames: 250,
+            start_time: 1_000_000,
+            last_time: 101_000_000,
+        });
+        assert_eq!(times.frames, 250);
+        assert_eq!(times.start, NDITime::from_ffi(1_000_000));
+        assert_eq!(times.duration(), Duration::from_secs(10));
+
+        let reversed = RecordingTimes {
+            start: times.last,
+            last: times.start,
+            ..times
+        };
+        assert_eq!(reversed.duration(), Duration::ZERO);
+    }
+}
@@ -1,6 +1,11 @@
 use std::fmt::{Debug, Display};
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "UncheckedResolution")
+)]
 pub struct Resolution {
     pub x: usize,
     pub y: usize,
@@ -110,6 +115,24 @@ impl Debug for Resolution {
     }
 }
 
+/// Deserialized resolution that still needs to be checked with [Resolution::is_safe]
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedResolution {
+    x: usize,
+    y: usize,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedResolution> for Resolution {
+    type Error = String;
+
+    fn try_from(res: UncheckedResolution) -> Result<Self, Self::Error> {
+        Resolution::try_new(res.x, res.y)
+            .ok_or_else(|| format!("Resolution is not safe: {}x{}", res.x, res.y))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,4 +160,16 @@ mod tests {
         // mul overflow
         assert_unsafe(i32::MAX as usize, 2);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let res = Resolution::new(1920, 1080);
+        let json = serde_json::to_string(&res).unwrap();
+        assert_eq!(json, r#"{"x":1920,"y":1080}"#);
+        assert_eq!(serde_json::from_str::<Resolution>(&json).unwrap(), res);
+
+        assert!(serde_json::from_str::<Resolution>(r#"{"x":0,"y":1080}"#).is_err());
+        assert!(serde_json::from_str::<Resolution>(r#"{"x":1921,"y":1080}"#).is_err());
+    }
 }
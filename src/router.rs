@@ -16,13 +16,14 @@ use static_assertions::assert_impl_all;
 use crate::{
     bindings,
     source::{NDISourceLike, NDISourceRef},
-    util::{SourceNameError, validate_source_name},
+    util::{GroupNameError, SourceNameError, validate_groups, validate_source_name},
 };
 
 /// Builder for [NDIRouter]
 #[derive(Debug, Clone)]
 pub struct NDIRouterBuilder {
     name: CString,
+    groups: Option<CString>,
 }
 assert_impl_all!(NDIRouterBuilder: Send, Sync);
 
@@ -35,14 +36,30 @@ impl NDIRouterBuilder {
     pub fn new(name: &str) -> Result<Self, SourceNameError> {
         Ok(Self {
             name: validate_source_name(name)?,
+            groups: None,
         })
     }
 
+    /// Sets the groups the routing source is placed into, if not set the system default groups are used.
+    ///
+    /// See [crate::sender::NDISenderBuilder::groups] for more information about groups
+    pub fn groups<I, S>(mut self, groups: I) -> Result<Self, GroupNameError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.groups = Some(validate_groups(groups)?);
+        Ok(self)
+    }
+
     pub fn build(self) -> Result<NDIRouter, NDIRouterBuilderError> {
         let options = bindings::NDIlib_routing_create_t {
-            // We only need the name for the constructor
+            // We only need the name and groups for the constructor
             p_ndi_name: self.name.as_ptr(),
-            p_groups: std::ptr::null(),
+            p_groups: self
+                .groups
+                .as_ref()
+                .map_or(std::ptr::null(), |s| s.as_ptr()),
         };
         let handle = unsafe { bindings::NDIlib_routing_create(&options) };
 
@@ -18,6 +18,7 @@ use crate::{
     frame::{
         audio::AudioFrame,
         generic::{AsFFIReadable, AsFFIWritable, FFIReadablePtrError},
+        interleaved::{InterleavedAudio, InterleavedSample},
         metadata::MetadataFrame,
         video::VideoFrame,
     },
@@ -268,6 +269,16 @@ impl NDISender {
         Ok(())
     }
 
+    /// Sends an interleaved audio buffer, it is converted to a planar [AudioFrame] first.
+    ///
+    /// C equivalent: `NDIlib_util_send_send_audio_interleaved_16s`/`_32s`/`_32f`
+    pub fn send_audio_interleaved<S: InterleavedSample>(
+        &self,
+        audio: &InterleavedAudio<S>,
+    ) -> Result<(), SendFrameError> {
+        self.send_audio(&audio.to_frame())
+    }
+
     pub fn send_metadata(&self, frame: &MetadataFrame) -> Result<(), SendFrameError> {
         let ptr = frame.to_ffi_send_frame_ptr().map_err(|err| match err {
             FFIReadablePtrError::NotReadable(desc) => SendFrameError::NotSendable(desc),
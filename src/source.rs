@@ -1,13 +1,16 @@
 //! Source descriptors are used to get the name of local and discovered senders as well as telling a receiver unambiguously which source to connect to
 //!
 //! A source descriptor contains the name (exposed via `.name()`) and
-//! optional internal connection info (not exposed, used to avoid creating an internal finder to resolve the name)
+//! optional connection info (exposed via `.url_address()`, used to avoid creating an internal finder to resolve the name)
 
 use std::{
+    error::Error,
     ffi::{CStr, CString},
-    fmt::Debug,
+    fmt::{Debug, Display},
     marker::PhantomData,
+    net::SocketAddr,
     ptr,
+    str::FromStr,
 };
 
 use static_assertions::assert_impl_all;
@@ -59,7 +62,7 @@ unsafe impl<S: NDISourceLike> NDISourceLike for Option<S> {
 /// This is a short-lived/reference source descriptor
 ///
 /// A source descriptor contains the name (exposed via [NDISourceRef::name]) and
-/// optional internal connection info (not exposed, used to avoid creating an internal finder to resolve the name)
+/// optional connection info (exposed via [NDISourceRef::url_address], used to avoid creating an internal finder to resolve the name)
 ///
 /// C equivalent: `NDIlib_source_t`
 #[derive(Clone)]
@@ -91,6 +94,30 @@ impl<'a> NDISourceRef<'a> {
         self.name
     }
 
+    /// Gets the connection address (`IP:port`) of the source, if the SDK provided one
+    pub fn url_address(&self) -> Option<&'a CStr> {
+        let url_address = unsafe { self.raw.__bindgen_anon_1.p_url_address };
+        if url_address.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(url_address) })
+        }
+    }
+
+    /// Parses the connection address, `None` if there is none or it is not a `IP:port` pair
+    pub fn socket_address(&self) -> Option<SocketAddr> {
+        parse_socket_address(self.url_address()?.to_str().ok()?)
+    }
+
+    /// Parses the name into machine and stream name, see [NDISourceName]
+    pub fn parsed_name(&self) -> Result<NDISourceName, NDISourceNameError> {
+        let name = self
+            .name
+            .to_str()
+            .map_err(|_| NDISourceNameError::InvalidUtf8)?;
+        NDISourceName::parse(name)
+    }
+
     /// Converts it to an owned [NDISource] by cloning all information
     pub fn to_owned(&self) -> NDISource {
         let descriptor_anon_1 = self.raw.__bindgen_anon_1;
@@ -142,8 +169,13 @@ impl std::fmt::Debug for NDISourceRef<'_> {
 /// long-lived/owned source descriptor
 ///
 /// A source descriptor contains the name (exposed via [NDISource::name]) and
-/// optional internal connection info (not exposed, used to avoid creating an internal finder to resolve the name)
+/// optional connection info (exposed via [NDISource::url_address], used to avoid creating an internal finder to resolve the name)
 #[derive(Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "SerdeSource", try_from = "SerdeSource")
+)]
 pub struct NDISource {
     name: String,
     name_c: CString,
@@ -162,13 +194,44 @@ impl NDISource {
         })
     }
 
+    /// Creates a source that connects directly to the given address without discovery
+    pub fn from_name_and_address(name: &str, address: SocketAddr) -> Result<Self, SourceNameError> {
+        let mut source = Self::from_name(name)?;
+        source.descriptor_anon_1 = Some(
+            CString::new(address.to_string())
+                .expect("[Invariant Error] Socket address contains null characters"),
+        );
+        Ok(source)
+    }
+
     pub fn name(&self) -> &str {
         self.name.as_str()
     }
 
+    /// Gets the connection address (`IP:port`) of the source, if known
+    pub fn url_address(&self) -> Option<&CStr> {
+        self.descriptor_anon_1.as_deref()
+    }
+
+    /// Parses the connection address, `None` if there is none or it is not a `IP:port` pair
+    pub fn socket_address(&self) -> Option<SocketAddr> {
+        parse_socket_address(self.url_address()?.to_str().ok()?)
+    }
+
     pub fn name_c_str(&self) -> &CStr {
         &self.name_c
     }
+
+    /// Creates a source from its machine and stream name
+    pub fn from_parsed_name(name: &NDISourceName) -> Self {
+        NDISource::from_name(&name.full_name())
+            .expect("[Invariant Error] NDISourceName was not validated")
+    }
+
+    /// Parses the name into machine and stream name, see [NDISourceName]
+    pub fn parsed_name(&self) -> Result<NDISourceName, NDISourceNameError> {
+        NDISourceName::parse(&self.name)
+    }
 }
 
 unsafe impl NDISourceLike for NDISource {
@@ -201,3 +264,311 @@ impl std::fmt::Debug for NDISource {
             .finish()
     }
 }
+
+/// Serialized form of [NDISource]
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerdeSource {
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url_address: Option<String>,
+}
+
+#[cfg(feature = "serde")]
+impl From<NDISource> for SerdeSource {
+    fn from(source: NDISource) -> Self {
+        SerdeSource {
+            url_address: source
+                .url_address()
+                .map(|url| url.to_string_lossy().into_owned()),
+            name: source.name,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SerdeSource> for NDISource {
+    type Error = String;
+
+    fn try_from(source: SerdeSource) -> Result<Self, Self::Error> {
+        let mut ndi_source = NDISource::from_name(&source.name).map_err(|e| e.to_string())?;
+        if let Some(url_address) = source.url_address {
+            ndi_source.descriptor_anon_1 = Some(
+                CString::new(url_address)
+                    .map_err(|_| "URL address contains null characters".to_owned())?,
+            );
+        }
+        Ok(ndi_source)
+    }
+}
+
+/// Parses an `IP:port` address, the SDK may use a `ndi://` prefix
+fn parse_socket_address(address: &str) -> Option<SocketAddr> {
+    let address = address.trim();
+    address
+        .strip_prefix("ndi://")
+        .unwrap_or(address)
+        .parse()
+        .ok()
+}
+
+/// A source name split into its parts
+///
+/// NDI source names follow the `MACHINE (Stream Name)` convention. The stream name is the content
+/// of the last top-level parentheses, so it may contain parentheses itself: `STUDIO-PC (Cam (1))`
+/// has the machine `STUDIO-PC` and the stream `Cam (1)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NDISourceName {
+    machine: String,
+    stream: String,
+}
+
+impl NDISourceName {
+    /// Creates a source name from its parts, surrounding whitespace is removed
+    ///
+    /// The resulting full name is checked with [validate_source_name].
+    pub fn new(machine: &str, stream: &str) -> Result<Self, NDISourceNameError> {
+        let machine = machine.trim();
+        let stream = stream.trim();
+
+        if machine.is_empty() {
+            Err(NDISourceNameError::EmptyMachine)?;
+        }
+        if stream.is_empty() {
+            Err(NDISourceNameError::EmptyStream)?;
+        }
+        if !parentheses_balanced(stream) {
+            Err(NDISourceNameError::UnbalancedParentheses)?;
+        }
+
+        let name = NDISourceName {
+            machine: machine.to_owned(),
+            stream: stream.to_owned(),
+        };
+        validate_source_name(&name.full_name()).map_err(NDISourceNameError::InvalidName)?;
+        Ok(name)
+    }
+
+    /// Parses a full source name
+    pub fn parse(name: &str) -> Result<Self, NDISourceNameError> {
+        let name = name.trim();
+        let inner = name
+            .strip_suffix(')')
+            .ok_or(NDISourceNameError::MissingStream)?;
+
+        // Find the parenthesis matching the last one
+        let mut depth = 1usize;
+        let mut open = None;
+        for (i, c) in inner.char_indices().rev() {
+            match c {
+                ')' => depth += 1,
+                '(' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        open = Some(i);
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let open = open.ok_or(NDISourceNameError::UnbalancedParentheses)?;
+
+        Self::new(&inner[..open], &inner[open + 1..])
+    }
+
+    /// The name of the machine the source is running on
+    pub fn machine(&self) -> &str {
+        &self.machine
+    }
+
+    /// The name of the stream on the machine
+    pub fn stream(&self) -> &str {
+        &self.stream
+    }
+
+    /// Checks if the source runs on the given machine, ignoring case
+    pub fn is_on_machine(&self, machine: &str) -> bool {
+        self.machine.to_lowercase() == machine.trim().to_lowercase()
+    }
+
+    /// The full name in the `MACHINE (Stream Name)` format
+    pub fn full_name(&self) -> String {
+        self.to_string()
+    }
+}
+
+fn parentheses_balanced(s: &str) -> bool {
+    let mut depth = 0usize;
+    for c in s.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => match depth.checked_sub(1) {
+                Some(d) => depth = d,
+                None => return false,
+            },
+            _ => {}
+        }
+    }
+    depth == 0
+}
+
+impl Display for NDISourceName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.machine, self.stream)
+    }
+}
+
+impl FromStr for NDISourceName {
+    type Err = NDISourceNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// see [NDISourceName] for more information
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NDISourceNameError {
+    /// The name does not end with a stream name in parentheses
+    MissingStream,
+    /// The parentheses in the name do not match
+    UnbalancedParentheses,
+    EmptyMachine,
+    EmptyStream,
+    /// The name is not valid UTF-8
+    InvalidUtf8,
+    /// The full name is not a valid source name
+    InvalidName(SourceNameError),
+}
+
+impl Display for NDISourceNameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingStream => {
+                f.write_str("Source name does not follow the `MACHINE (Stream)` convention")
+            }
+            Self::UnbalancedParentheses => f.write_str("Source name has unbalanced parentheses"),
+            Self::EmptyMachine => f.write_str("Machine name is empty"),
+            Self::EmptyStream => f.write_str("Stream name is empty"),
+            Self::InvalidUtf8 => f.write_str("Source name is not valid UTF-8"),
+            Self::InvalidName(err) => write!(f, "Invalid source name: {err}"),
+        }
+    }
+}
+
+impl Error for NDISourceNameError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidName(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_name() {
+        let name = NDISourceName::parse("STUDIO-PC (Camera 1)").unwrap();
+        assert_eq!(name.machine(), "STUDIO-PC");
+        assert_eq!(name.stream(), "Camera 1");
+        assert_eq!(name.full_name(), "STUDIO-PC (Camera 1)");
+
+        let nested: NDISourceName = "STUDIO-PC (Cam (1) (wide))".parse().unwrap();
+        assert_eq!(nested.machine(), "STUDIO-PC");
+        assert_eq!(nested.stream(), "Cam (1) (wide)");
+
+        let machine_parens = NDISourceName::parse("PC (2) (Output)").unwrap();
+        assert_eq!(machine_parens.machine(), "PC (2)");
+        assert_eq!(machine_parens.stream(), "Output");
+
+        let no_space = NDISourceName::parse("PC(Output)").unwrap();
+        assert_eq!(no_space.full_name(), "PC (Output)");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        use NDISourceNameError::*;
+        for (name, err) in [
+            ("STUDIO-PC", MissingStream),
+            ("STUDIO-PC (Cam) x", MissingStream),
+            ("STUDIO-PC Cam)", UnbalancedParentheses),
+            ("STUDIO-PC (Cam))", UnbalancedParentheses),
+            ("(Cam)", EmptyMachine),
+            ("STUDIO-PC ( )", EmptyStream),
+        ] {
+            assert_eq!(NDISourceName::parse(name), Err(err), "{name}");
+        }
+
+        assert_eq!(
+            NDISourceName::new("PC", "Cam (1"),
+            Err(UnbalancedParentheses)
+        );
+        assert_eq!(
+            NDISourceName::new("PC", &"x".repeat(300)),
+            Err(InvalidName(SourceNameError::TooLong))
+        );
+        assert!(matches!(
+            NDISourceName::new("PC", "Cam\0"),
+            Err(InvalidName(SourceNameError::NulError(_)))
+        ));
+    }
+
+    #[test]
+    fn test_address() {
+        let address: SocketAddr = "192.168.1.20:5961".parse().unwrap();
+        let source = NDISource::from_name_and_address("PC (Cam)", address).unwrap();
+        assert_eq!(source.url_address(), Some(c"192.168.1.20:5961"));
+        assert_eq!(source.socket_address(), Some(address));
+        assert_eq!(
+            NDISource::from_name("PC (Cam)").unwrap().url_address(),
+            None
+        );
+
+        let v6: SocketAddr = "[fe80::1]:5960".parse().unwrap();
+        let source = NDISource::from_name_and_address("PC (Cam)", v6).unwrap();
+        let source_ref =
+            source.with_descriptor(|ptr| unsafe { NDISourceRef::from(*ptr) }.to_owned());
+        assert_eq!(source_ref, source);
+        assert_eq!(source_ref.socket_address(), Some(v6));
+
+        assert_eq!(
+            parse_socket_address("ndi://10.0.0.1:5961"),
+            "10.0.0.1:5961".parse().ok()
+        );
+        assert_eq!(parse_socket_address("studio-pc:5961"), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let source =
+            NDISource::from_name_and_address("PC (Cam)", "10.0.0.1:5961".parse().unwrap()).unwrap();
+        let json = serde_json::to_string(&source).unwrap();
+        assert_eq!(json, r#"{"name":"PC (Cam)","url_address":"10.0.0.1:5961"}"#);
+        assert_eq!(serde_json::from_str::<NDISource>(&json).unwrap(), source);
+
+        let source = NDISource::from_name("PC (Cam)").unwrap();
+        assert_eq!(
+            serde_json::from_str::<NDISource>(r#"{"name":"PC (Cam)"}"#).unwrap(),
+            source
+        );
+        assert!(serde_json::from_str::<NDISource>(r#"{"name":"PC\u0000"}"#).is_err());
+    }
+
+    #[test]
+    fn test_machine_matching() {
+        let name = NDISourceName::new(" Studio-PC ", "Cam").unwrap();
+        assert!(name.is_on_machine("STUDIO-PC"));
+        assert!(name.is_on_machine("studio-pc "));
+        assert!(!name.is_on_machine("studio"));
+
+        let source = NDISource::from_parsed_name(&name);
+        assert_eq!(source.name(), "Studio-PC (Cam)");
+        assert_eq!(source.parsed_name(), Ok(name));
+    }
+}
@@ -0,0 +1,239 @@
+//! Receiver performance and queue statistics
+
+use std::time::Instant;
+
+use crate::{bindings, receiver::NDIReceiver};
+
+/// Number of frames per frame type
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FrameCounts {
+    pub video: u64,
+    pub audio: u64,
+    pub metadata: u64,
+}
+
+impl FrameCounts {
+    pub fn new(video: u64, audio: u64, metadata: u64) -> Self {
+        FrameCounts {
+            video,
+            audio,
+            metadata,
+        }
+    }
+
+    /// The number of frames of all types
+    pub fn sum(&self) -> u64 {
+        self.video + self.audio + self.metadata
+    }
+
+    /// Counts since an earlier snapshot, counters that went backwards (e.g. after a reconnect) yield 0
+    pub fn since(&self, earlier: &FrameCounts) -> FrameCounts {
+        FrameCounts {
+            video: self.video.saturating_sub(earlier.video),
+            audio: self.audio.saturating_sub(earlier.audio),
+            metadata: self.metadata.saturating_sub(earlier.metadata),
+        }
+    }
+
+    pub(crate) fn from_ffi_performance(counts: &bindings::NDIlib_recv_performance_t) -> Self {
+        FrameCounts {
+            video: counts.video_frames.max(0) as u64,
+            audio: counts.audio_frames.max(0) as u64,
+            metadata: counts.metadata_frames.max(0) as u64,
+        }
+    }
+
+    pub(crate) fn from_ffi_queue(counts: &bindings::NDIlib_recv_queue_t) -> Self {
+        FrameCounts {
+            video: counts.video_frames.max(0) as u64,
+            audio: counts.audio_frames.max(0) as u64,
+            metadata: counts.metadata_frames.max(0) as u64,
+        }
+    }
+}
+
+/// Total and dropped frames since the receiver was created
+///
+/// C equivalent: `NDIlib_recv_performance_t` (`p_total` and `p_dropped`)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NDIRecvPerformance {
+    pub total: FrameCounts,
+    pub dropped: FrameCounts,
+}
+
+/// A snapshot of the receiver statistics, see [NDIReceiver::get_stats]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NDIRecvStats {
+    pub performance: NDIRecvPerformance,
+    /// Frames that are currently queued and were not yet received by the application
+    ///
+    /// C equivalent: `NDIlib_recv_queue_t`
+    pub queue: FrameCounts,
+    /// The time the snapshot was taken
+    pub timestamp: Instant,
+}
+
+impl NDIRecvStats {
+    /// Computes the drop rates between an earlier snapshot and this one
+    pub fn drop_rates_since(&self, earlier: &NDIRecvStats) -> DropRates {
+        let total = self.performance.total.since(&earlier.performance.total);
+        let dropped = self.performance.dropped.since(&earlier.performance.dropped);
+
+        let rate = |dropped: u64, total: u64| {
+            if total == 0 {
+                None
+            } else {
+                Some(dropped as f64 / total as f64)
+            }
+        };
+
+        DropRates {
+            video: rate(dropped.video, total.video),
+            audio: rate(dropped.audio, total.audio),
+            metadata: rate(dropped.metadata, total.metadata),
+            dropped,
+            elapsed: self.timestamp.saturating_duration_since(earlier.timestamp),
+        }
+    }
+}
+
+/// Drop rates between two [NDIRecvStats] snapshots
+///
+/// The rates are the fraction of dropped frames (`0..=1`), `None` if no frames of that type were received.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DropRates {
+    pub video: Option<f64>,
+    pub audio: Option<f64>,
+    pub metadata: Option<f64>,
+    /// Absolute number of dropped frames
+    pub dropped: FrameCounts,
+    /// Time between the snapshots
+    pub elapsed: std::time::Duration,
+}
+
+impl DropRates {
+    /// Checks if any of the drop rates exceeds the threshold
+    pub fn exceeds(&self, threshold: f64) -> bool {
+        [self.video, self.audio, self.metadata]
+            .into_iter()
+            .flatten()
+            .any(|rate| rate > threshold)
+    }
+}
+
+// Statistics
+impl NDIReceiver {
+    /// Returns the number of total and dropped frames since the receiver was created.
+    pub fn get_performance(&self) -> NDIRecvPerformance {
+        let mut total = bindings::NDIlib_recv_performance_t {
+            video_frames: 0,
+            audio_frames: 0,
+            metadata_frames: 0,
+        };
+        let mut dropped = total;
+
+        unsafe {
+            bindings::NDIlib_recv_get_performance(self.handle.raw_ptr(), &mut total, &mut dropped)
+        };
+
+        NDIRecvPerformance {
+            total: FrameCounts::from_ffi_performance(&total),
+            dropped: FrameCounts::from_ffi_performance(&dropped),
+        }
+    }
+
+    /// Returns the number of frames that are queued and were not yet received by the application.
+    pub fn get_queue(&self) -> FrameCounts {
+        let mut queue = bindings::NDIlib_recv_queue_t {
+            video_frames: 0,
+            audio_frames: 0,
+            metadata_frames: 0,
+        };
+
+        unsafe { bindings::NDIlib_recv_get_queue(self.handle.raw_ptr(), &mut queue) };
+
+        FrameCounts::from_ffi_queue(&queue)
+    }
+
+    /// Returns a snapshot of the performance and queue statistics.
+    ///
+    /// ```rust,no_run
+    /// # use ndi_sdk_sys::receiver::NDIReceiver;
+    /// # fn monitor(receiver: &NDIReceiver) {
+    /// let before = receiver.get_stats();
+    /// std::thread::sleep(std::time::Duration::from_secs(5));
+    /// let rates = receiver.get_stats().drop_rates_since(&before);
+    /// if rates.exceeds(0.01) {
+    ///     eprintln!("Link degraded: {rates:?}");
+    /// }
+    /// # }
+    /// ```
+    pub fn get_stats(&self) -> NDIRecvStats {
+        NDIRecvStats {
+            performance: self.get_performance(),
+            queue: self.get_queue(),
+            timestamp: Instant::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn stats(total: FrameCounts, dropped: FrameCounts, timestamp: Instant) -> NDIRecvStats {
+        NDIRecvStats {
+            performance: NDIRecvPerformance { total, dropped },
+            queue: FrameCounts::default(),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_drop_rates() {
+        let start = Instant::now();
+        let before = stats(
+            FrameCounts::new(100, 200, 5),
+            FrameCounts::new(10, 0, 1),
+            start,
+        );
+        let after = stats(
+            FrameCounts::new(200, 400, 5),
+            FrameCounts::new(15, 2, 1),
+            start + Duration::from_secs(2),
+        );
+
+        let rates = after.drop_rates_since(&before);
+        assert_eq!(rates.video, Some(0.05));
+        assert_eq!(rates.audio, Some(0.01));
+        assert_eq!(rates.metadata, None);
+        assert_eq!(rates.dropped, FrameCounts::new(5, 2, 0));
+        assert_eq!(rates.elapsed, Duration::from_secs(2));
+        assert!(rates.exceeds(0.02));
+        assert!(!rates.exceeds(0.05));
+    }
+
+    #[test]
+    fn test_counter_reset() {
+        let now = Instant::now();
+        let before = stats(FrameCounts::new(100, 0, 0), FrameCounts::new(50, 0, 0), now);
+        let after = stats(FrameCounts::new(10, 0, 0), FrameCounts::new(1, 0, 0), now);
+
+        let rates = after.drop_rates_since(&before);
+        assert_eq!(rates.video, None);
+        assert_eq!(rates.dropped.sum(), 0);
+    }
+
+    #[test]
+    fn test_from_ffi() {
+        let counts = FrameCounts::from_ffi_queue(&bindings::NDIlib_recv_queue_t {
+            video_frames: 3,
+            audio_frames: -1,
+            metadata_frames: 7,
+        });
+        assert_eq!(counts, FrameCounts::new(3, 0, 7));
+        assert_eq!(counts.sum(), 10);
+    }
+}
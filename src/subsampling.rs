@@ -11,6 +11,7 @@ use std::fmt::Display;
 ///
 /// <https://docs.ndi.video/all/using-ndi/ndi-for-video/digital-video-basics#chroma-subsampling>
 #[derive(Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Subsampling {
     pub x_ref: u8,
     pub x_samples: u8,
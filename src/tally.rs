@@ -6,6 +6,7 @@ use crate::bindings;
 ///
 /// C equivalent: `NDIlib_tally_t`
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Tally {
     pub program: bool,
     pub preview: bool,
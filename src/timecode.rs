@@ -1,5 +1,11 @@
-use std::time::{Duration, SystemTime};
+use std::{
+    error::Error,
+    fmt::Display,
+    ops::{Add, AddAssign, Neg, Sub, SubAssign},
+    time::{Duration, SystemTime},
+};
 
+use num::Rational32;
 use static_assertions::{const_assert, const_assert_eq};
 
 use crate::bindings;
@@ -14,6 +20,8 @@ use crate::bindings;
 /// Since the timecode is stored in UTC within NDI, communicating timecode time of day for non-UTC time zones requires a translation.
 #[repr(transparent)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct NDITime(i64);
 
 const NDI_TIME_DEFAULT: i64 = i64::MAX;
@@ -27,6 +35,25 @@ impl Default for NDITime {
     }
 }
 
+/// Ticks of [NDITime] per second
+const TICKS_PER_SECOND: i64 = 10_000_000;
+const TICKS_PER_DAY: i64 = 24 * 60 * 60 * TICKS_PER_SECOND;
+
+const_assert!(TICKS_PER_SECOND < i32::MAX as i64);
+
+/// Converts a duration to ticks, sub-tick precision is truncated
+fn duration_to_ticks(duration: Duration) -> Option<i64> {
+    let secs = i64::try_from(duration.as_secs()).ok()?;
+    secs.checked_mul(TICKS_PER_SECOND)?
+        .checked_add((duration.subsec_nanos() / 100) as i64)
+}
+
+fn ticks_to_duration(ticks: u64) -> Duration {
+    let secs = ticks / TICKS_PER_SECOND as u64;
+    let nanos = (ticks % TICKS_PER_SECOND as u64 * 100) as u32;
+    Duration::new(secs, nanos)
+}
+
 impl NDITime {
     #[inline]
     pub fn to_ffi(self) -> i64 {
@@ -38,37 +65,864 @@ impl NDITime {
         Self(time)
     }
 
-    /// **This API is unstable**
+    /// Creates a timestamp from the time passed since the Unix epoch
     ///
-    /// Converts this timestamp to [SystemTime]
-    pub fn to_utc(self) -> Option<SystemTime> {
-        fn to_duration(time: u64) -> Duration {
-            let secs = time / 10_000_000;
-            let sub_100ns = time % 10_000_000;
-
-            const_assert!(10_000_000 < i32::MAX);
-
-            let nanos = (sub_100ns * 100) as u32;
+    /// Returns `None` if the time is out of range
+    pub fn from_unix_duration(since_epoch: Duration) -> Option<Self> {
+        Self::from_ticks(duration_to_ticks(since_epoch)?)
+    }
 
-            Duration::new(secs, nanos)
+    /// Creates a timestamp from a [SystemTime], sub-100 ns precision is truncated
+    ///
+    /// Returns `None` if the time is out of range (about ±29000 years around 1970)
+    pub fn from_system_time(time: SystemTime) -> Option<Self> {
+        match time.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(since_epoch) => Self::from_unix_duration(since_epoch),
+            Err(before_epoch) => Self::from_ticks(-duration_to_ticks(before_epoch.duration())?),
         }
+    }
+
+    /// The current system time
+    pub fn now() -> Self {
+        Self::from_system_time(SystemTime::now())
+            .expect("[Invariant Error] System time is out of the range of NDITime")
+    }
 
+    /// Converts this timestamp to [SystemTime], `None` for [NDITime::UNDEFINED]
+    pub fn to_utc(self) -> Option<SystemTime> {
         if self.is_default() {
             None
+        } else if self.0 >= 0 {
+            Some(SystemTime::UNIX_EPOCH + ticks_to_duration(self.0 as u64))
         } else {
-            Some(if self.0 >= 0 {
-                SystemTime::UNIX_EPOCH + to_duration(self.0 as u64)
-            } else {
-                SystemTime::UNIX_EPOCH - to_duration(self.0.saturating_abs() as u64)
-            })
+            SystemTime::UNIX_EPOCH.checked_sub(ticks_to_duration(self.0.unsigned_abs()))
         }
     }
 
+    /// Checks if this is the [NDITime::UNDEFINED]/[NDITime::SYNTHESIZE] sentinel
     pub fn is_default(self) -> bool {
         self.0 == NDI_TIME_DEFAULT
     }
 
+    /// Wraps ticks, the sentinel value is treated as an overflow
+    fn from_ticks(ticks: i64) -> Option<Self> {
+        if ticks == NDI_TIME_DEFAULT {
+            None
+        } else {
+            Some(Self(ticks))
+        }
+    }
+
+    /// Adds a duration, returns `None` on overflow or if `self` is [NDITime::UNDEFINED]
+    pub fn checked_add(self, duration: Duration) -> Option<Self> {
+        if self.is_default() {
+            return None;
+        }
+        Self::from_ticks(self.0.checked_add(duration_to_ticks(duration)?)?)
+    }
+
+    /// Subtracts a duration, returns `None` on overflow or if `self` is [NDITime::UNDEFINED]
+    pub fn checked_sub(self, duration: Duration) -> Option<Self> {
+        if self.is_default() {
+            return None;
+        }
+        Self::from_ticks(self.0.checked_sub(duration_to_ticks(duration)?)?)
+    }
+
+    /// Adds a duration, clamped to [NDITime::MAX]. [NDITime::UNDEFINED] stays undefined.
+    pub fn saturating_add(self, duration: Duration) -> Self {
+        if self.is_default() {
+            self
+        } else {
+            self.checked_add(duration).unwrap_or(Self::MAX)
+        }
+    }
+
+    /// Subtracts a duration, clamped to [NDITime::MIN]. [NDITime::UNDEFINED] stays undefined.
+    pub fn saturating_sub(self, duration: Duration) -> Self {
+        if self.is_default() {
+            self
+        } else {
+            self.checked_sub(duration).unwrap_or(Self::MIN)
+        }
+    }
+
+    /// The signed time from `earlier` to `self`
+    ///
+    /// Returns `None` if one of the timestamps is [NDITime::UNDEFINED] or on overflow
+    pub fn signed_duration_since(self, earlier: NDITime) -> Option<NDITimeDelta> {
+        if self.is_default() || earlier.is_default() {
+            return None;
+        }
+        Some(NDITimeDelta(self.0.checked_sub(earlier.0)?))
+    }
+
+    /// Received frames without timestamp and frames to send with a timestamp generated by the SDK
+    /// both use this value. Arithmetic keeps it as is or returns `None` (checked variants).
     pub const UNDEFINED: Self = Self(NDI_TIME_DEFAULT);
     /// Advises the SDK to automatically generate a timecode for this frame from the system clock
+    ///
+    /// This is the same value as [NDITime::UNDEFINED]
     pub const SYNTHESIZE: Self = Self(NDI_TIME_DEFAULT);
+    /// 1/1/1970 00:00 UTC
+    pub const UNIX_EPOCH: Self = Self(0);
+    /// The earliest representable time
+    pub const MIN: Self = Self(i64::MIN);
+    /// The latest representable time (the next tick is the sentinel value)
+    pub const MAX: Self = Self(NDI_TIME_DEFAULT - 1);
+}
+
+/// Adds a duration, [NDITime::UNDEFINED] stays undefined
+///
+/// # Panics
+///
+/// Panics on overflow, see [NDITime::checked_add]
+impl Add<Duration> for NDITime {
+    type Output = NDITime;
+
+    fn add(self, duration: Duration) -> NDITime {
+        if self.is_default() {
+            self
+        } else {
+            self.checked_add(duration)
+                .expect("overflow when adding duration to NDITime")
+        }
+    }
+}
+
+/// Subtracts a duration, [NDITime::UNDEFINED] stays undefined
+///
+/// # Panics
+///
+/// Panics on overflow, see [NDITime::checked_sub]
+impl Sub<Duration> for NDITime {
+    type Output = NDITime;
+
+    fn sub(self, duration: Duration) -> NDITime {
+        if self.is_default() {
+            self
+        } else {
+            self.checked_sub(duration)
+                .expect("overflow when subtracting duration from NDITime")
+        }
+    }
+}
+
+impl AddAssign<Duration> for NDITime {
+    fn add_assign(&mut self, duration: Duration) {
+        *self = *self + duration;
+    }
+}
+
+impl SubAssign<Duration> for NDITime {
+    fn sub_assign(&mut self, duration: Duration) {
+        *self = *self - duration;
+    }
+}
+
+/// see [NDITime::signed_duration_since]
+impl Sub for NDITime {
+    type Output = Option<NDITimeDelta>;
+
+    fn sub(self, earlier: NDITime) -> Option<NDITimeDelta> {
+        self.signed_duration_since(earlier)
+    }
+}
+
+impl TryFrom<SystemTime> for NDITime {
+    type Error = NDITimeRangeError;
+
+    fn try_from(time: SystemTime) -> Result<Self, Self::Error> {
+        Self::from_system_time(time).ok_or(NDITimeRangeError)
+    }
+}
+
+/// The time is outside the range of [NDITime]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NDITimeRangeError;
+
+impl Display for NDITimeRangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Time is out of the range of NDITime")
+    }
+}
+
+impl Error for NDITimeRangeError {}
+
+/// A signed difference between two [NDITime]s in 100 ns ticks
+///
+/// ```rust,no_run
+/// # use ndi_sdk_sys::frame::video::VideoFrame;
+/// # let frame = VideoFrame::new();
+/// let latency = frame.recv_time() - frame.send_time();
+/// println!("{:?}", latency.map(|l| l.as_secs_f64()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct NDITimeDelta(i64);
+
+impl NDITimeDelta {
+    pub const ZERO: Self = Self(0);
+
+    pub fn from_ticks(ticks: i64) -> Self {
+        Self(ticks)
+    }
+
+    /// The difference in 100 ns ticks
+    pub fn ticks(self) -> i64 {
+        self.0
+    }
+
+    /// Returns `None` if the duration is out of range
+    pub fn from_duration(duration: Duration) -> Option<Self> {
+        duration_to_ticks(duration).map(Self)
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    /// The duration, `None` if it is negative
+    pub fn to_duration(self) -> Option<Duration> {
+        if self.is_negative() {
+            None
+        } else {
+            Some(ticks_to_duration(self.0 as u64))
+        }
+    }
+
+    /// The absolute value of the difference
+    pub fn abs_duration(self) -> Duration {
+        ticks_to_duration(self.0.unsigned_abs())
+    }
+
+    /// Negates the delta, `None` if it is the most negative delta
+    pub fn checked_neg(self) -> Option<Self> {
+        self.0.checked_neg().map(Self)
+    }
+
+    pub fn as_secs_f64(self) -> f64 {
+        self.0 as f64 / TICKS_PER_SECOND as f64
+    }
+}
+
+/// Saturates at the maximum for the most negative delta, see [NDITimeDelta::checked_neg]
+impl Neg for NDITimeDelta {
+    type Output = NDITimeDelta;
+
+    fn neg(self) -> NDITimeDelta {
+        NDITimeDelta(self.0.saturating_neg())
+    }
+}
+
+/// Frame rates supported by [SMPTETimecode]
+///
+/// The fractional NTSC rates exist as drop-frame and non-drop-frame variants.
+/// Drop-frame timecode skips frame numbers `0` and `1` (`0` to `3` at 59.94) of every minute
+/// except every tenth, so the timecode stays in sync with the wall clock.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SMPTEFrameRate {
+    /// 24000/1001, non-drop-frame
+    Fps23_976,
+    Fps24,
+    Fps25,
+    /// 30000/1001, drop-frame
+    Fps29_97Drop,
+    /// 30000/1001, non-drop-frame
+    Fps29_97,
+    Fps30,
+    Fps50,
+    /// 60000/1001, drop-frame
+    Fps59_94Drop,
+    /// 60000/1001, non-drop-frame
+    Fps59_94,
+    Fps60,
+}
+
+impl SMPTEFrameRate {
+    /// Finds the timecode rate for a frame rate, `drop_frame` is only possible for 29.97 and 59.94
+    ///
+    /// The rate is compared by value, so unreduced rates like `30000/1000` are accepted.
+    pub fn from_rational(frame_rate: Rational32, drop_frame: bool) -> Option<Self> {
+        if *frame_rate.denom() == 0 {
+            return None;
+        }
+
+        let frame_rate = frame_rate.reduced();
+        let rate = match (*frame_rate.numer(), *frame_rate.denom()) {
+            (24_000, 1001) => Self::Fps23_976,
+            (24, 1) => Self::Fps24,
+            (25, 1) => Self::Fps25,
+            (30_000, 1001) if drop_frame => Self::Fps29_97Drop,
+            (30_000, 1001) => Self::Fps29_97,
+            (30, 1) => Self::Fps30,
+            (50, 1) => Self::Fps50,
+            (60_000, 1001) if drop_frame => Self::Fps59_94Drop,
+            (60_000, 1001) => Self::Fps59_94,
+            (60, 1) => Self::Fps60,
+            _ => None?,
+        };
+
+        if rate.is_drop_frame() == drop_frame {
+            Some(rate)
+        } else {
+            None
+        }
+    }
+
+    /// The actual frame rate
+    pub fn to_rational(self) -> Rational32 {
+        let nominal = self.nominal_fps() as i32;
+        if self.is_ntsc() {
+            Rational32::new_raw(nominal * 1000, 1001)
+        } else {
+            Rational32::new_raw(nominal, 1)
+        }
+    }
+
+    /// The number of frames per timecode second, e.g. 30 for 29.97
+    pub fn nominal_fps(self) -> u32 {
+        match self {
+            Self::Fps23_976 | Self::Fps24 => 24,
+            Self::Fps25 => 25,
+            Self::Fps29_97Drop | Self::Fps29_97 | Self::Fps30 => 30,
+            Self::Fps50 => 50,
+            Self::Fps59_94Drop | Self::Fps59_94 | Self::Fps60 => 60,
+        }
+    }
+
+    pub fn is_drop_frame(self) -> bool {
+        matches!(self, Self::Fps29_97Drop | Self::Fps59_94Drop)
+    }
+
+    fn is_ntsc(self) -> bool {
+        matches!(
+            self,
+            Self::Fps23_976
+                | Self::Fps29_97Drop
+                | Self::Fps29_97
+                | Self::Fps59_94Drop
+                | Self::Fps59_94
+        )
+    }
+
+    /// Frame numbers skipped at the start of a minute
+    fn dropped_frames(self) -> u32 {
+        if self.is_drop_frame() {
+            self.nominal_fps() / 15
+        } else {
+            0
+        }
+    }
+
+    /// The number of frames from `00:00:00:00` to `23:59:59:FF` (inclusive)
+    pub fn frames_per_day(self) -> u32 {
+        let nominal_per_10_minutes = self.nominal_fps() * 600;
+        let per_10_minutes = nominal_per_10_minutes - 9 * self.dropped_frames();
+        per_10_minutes * 6 * 24
+    }
+}
+
+/// A SMPTE `HH:MM:SS:FF` time of day timecode
+///
+/// The timecode wraps around after 24 hours. Drop-frame timecodes are displayed with `;` as the
+/// last separator (`01:00:00;00`).
+///
+/// ```rust
+/// # use ndi_sdk_sys::timecode::{SMPTEFrameRate, SMPTETimecode};
+/// let tc = SMPTETimecode::parse("00:00:59;29", SMPTEFrameRate::Fps29_97Drop).unwrap();
+/// assert_eq!((tc + 1).to_string(), "00:01:00;02");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SMPTETimecode {
+    /// Frames since midnight, always less than [SMPTEFrameRate::frames_per_day]
+    frame_count: u32,
+    rate: SMPTEFrameRate,
+}
+
+impl SMPTETimecode {
+    /// Creates a timecode from its components
+    pub fn new(
+        hours: u32,
+        minutes: u32,
+        seconds: u32,
+        frames: u32,
+        rate: SMPTEFrameRate,
+    ) -> Result<Self, SMPTETimecodeError> {
+        if hours >= 24 || minutes >= 60 || seconds >= 60 || frames >= rate.nominal_fps() {
+            Err(SMPTETimecodeError::OutOfRange)?;
+        }
+
+        let drop = rate.dropped_frames();
+        if seconds == 0 && !minutes.is_multiple_of(10) && frames < drop {
+            Err(SMPTETimecodeError::DroppedFrame)?;
+        }
+
+        let total_minutes = hours * 60 + minutes;
+        let nominal_count = (total_minutes * 60 + seconds) * rate.nominal_fps() + frames;
+        let frame_count = nominal_count - drop * (total_minutes - total_minutes / 10);
+
+        Ok(Self { frame_count, rate })
+    }
+
+    /// Creates a timecode from the number of frames since midnight
+    ///
+    /// Returns `None` if `frame_count` exceeds [SMPTEFrameRate::frames_per_day]
+    pub fn from_frame_count(frame_count: u32, rate: SMPTEFrameRate) -> Option<Self> {
+        if frame_count < rate.frames_per_day() {
+            Some(Self { frame_count, rate })
+        } else {
+            None
+        }
+    }
+
+    /// The time of day of an [NDITime] as timecode, this is the frame that contains the time.
+    ///
+    /// Returns `None` for [NDITime::UNDEFINED]
+    pub fn from_ndi_time(time: NDITime, rate: SMPTEFrameRate) -> Option<Self> {
+        if time.is_default() {
+            return None;
+        }
+
+        let ticks = time.to_ffi().rem_euclid(TICKS_PER_DAY) as i128;
+        let frame_rate = rate.to_rational();
+        let frames = ticks * *frame_rate.numer() as i128
+            / (TICKS_PER_SECOND as i128 * *frame_rate.denom() as i128);
+
+        // NTSC rates have slightly less frames per day than there are timecodes,
+        // drop-frame slightly more, so the count wraps like a clock would
+        let frame_count = (frames as u32) % rate.frames_per_day();
+        Some(Self { frame_count, rate })
+    }
+
+    /// Like [SMPTETimecode::from_ndi_time], but with the frame rate of a video frame
+    ///
+    /// Returns `None` if the frame rate is not supported
+    pub fn from_ndi_time_rational(
+        time: NDITime,
+        frame_rate: Rational32,
+        drop_frame: bool,
+    ) -> Option<Self> {
+        Self::from_ndi_time(time, SMPTEFrameRate::from_rational(frame_rate, drop_frame)?)
+    }
+
+    /// The start of the frame on the (UTC) day of `day`
+    ///
+    /// Returns `None` if the result is out of the range of [NDITime]
+    ///
+    /// Non-drop-frame timecodes at NTSC rates run slower than the clock, timecodes after about
+    /// `23:58:33` lie on the following day.
+    ///
+    /// Returns `None` for [NDITime::UNDEFINED]
+    pub fn to_ndi_time(self, day: NDITime) -> Option<NDITime> {
+        if day.is_default() {
+            return None;
+        }
+
+        let midnight = day
+            .to_ffi()
+            .checked_sub(day.to_ffi().rem_euclid(TICKS_PER_DAY))?;
+        NDITime::from_ticks(midnight.checked_add(self.ticks_since_midnight())?)
+    }
+
+    fn ticks_since_midnight(self) -> i64 {
+        let frame_rate = self.rate.to_rational();
+        let numer =
+            self.frame_count as i128 * TICKS_PER_SECOND as i128 * *frame_rate.denom() as i128;
+        let denom = *frame_rate.numer() as i128;
+        // rounding up ensures that from_ndi_time returns the same frame again
+        ((numer + denom - 1) / denom) as i64
+    }
+
+    /// Parses `HH:MM:SS:FF`, the last separator may also be `;` or `.`
+    pub fn parse(timecode: &str, rate: SMPTEFrameRate) -> Result<Self, SMPTETimecodeError> {
+        let bytes = timecode.as_bytes();
+        if bytes.len() != 11
+            || bytes[2] != b':'
+            || bytes[5] != b':'
+            || !matches!(bytes[8], b':' | b';' | b'.')
+        {
+            Err(SMPTETimecodeError::InvalidFormat)?;
+        }
+
+        let part = |start: usize| {
+            let digits = &bytes[start..start + 2];
+            if digits.iter().all(u8::is_ascii_digit) {
+                Ok((digits[0] - b'0') as u32 * 10 + (digits[1] - b'0') as u32)
+            } else {
+                Err(SMPTETimecodeError::InvalidFormat)
+            }
+        };
+
+        Self::new(part(0)?, part(3)?, part(6)?, part(9)?, rate)
+    }
+
+    pub fn rate(&self) -> SMPTEFrameRate {
+        self.rate
+    }
+
+    /// The number of frames since midnight
+    pub fn frame_count(&self) -> u32 {
+        self.frame_count
+    }
+
+    /// The count of nominal frames, including the frame numbers skipped by drop-frame timecode
+    fn nominal_count(&self) -> u32 {
+        let drop = self.rate.dropped_frames();
+        if drop == 0 {
+            return self.frame_count;
+        }
+
+        let per_minute = self.rate.nominal_fps() * 60 - drop;
+        let per_10_minutes = self.rate.nominal_fps() * 600 - 9 * drop;
+
+        let tens = self.frame_count / per_10_minutes;
+        let remainder = self.frame_count % per_10_minutes;
+
+        let skipped_minutes = if remainder < drop {
+            0
+        } else {
+            (remainder - drop) / per_minute
+        };
+
+        self.frame_count + drop * (9 * tens + skipped_minutes)
+    }
+
+    /// Splits the timecode into `(hours, minutes, seconds, frames)`
+    pub fn components(&self) -> (u32, u32, u32, u32) {
+        let fps = self.rate.nominal_fps();
+        let count = self.nominal_count();
+        let frames = count % fps;
+        let seconds = count / fps;
+        (seconds / 3600, seconds / 60 % 60, seconds % 60, frames)
+    }
+
+    pub fn hours(&self) -> u32 {
+        self.components().0
+    }
+
+    pub fn minutes(&self) -> u32 {
+        self.components().1
+    }
+
+    pub fn seconds(&self) -> u32 {
+        self.components().2
+    }
+
+    pub fn frames(&self) -> u32 {
+        self.components().3
+    }
+
+    /// Adds (or subtracts) frames, wrapping around midnight
+    pub fn add_frames(self, frames: i64) -> Self {
+        let frames_per_day = self.rate.frames_per_day() as i64;
+        let frame_count = (self.frame_count as i64 + frames).rem_euclid(frames_per_day);
+        Self {
+            frame_count: frame_count as u32,
+            rate: self.rate,
+        }
+    }
+
+    /// The number of frames from `earlier` to `self`, `None` if the rates differ
+    ///
+    /// This does not account for wrapping around midnight.
+    pub fn frames_since(&self, earlier: &Self) -> Option<i64> {
+        if self.rate == earlier.rate {
+            Some(self.frame_count as i64 - earlier.frame_count as i64)
+        } else {
+            None
+        }
+    }
+}
+
+impl Add<i64> for SMPTETimecode {
+    type Output = Self;
+
+    fn add(self, frames: i64) -> Self {
+        self.add_frames(frames)
+    }
+}
+
+impl Sub<i64> for SMPTETimecode {
+    type Output = Self;
+
+    fn sub(self, frames: i64) -> Self {
+        self.add_frames(-frames)
+    }
+}
+
+impl Display for SMPTETimecode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (hours, minutes, seconds, frames) = self.components();
+        let separator = if self.rate.is_drop_frame() { ';' } else { ':' };
+        write!(
+            f,
+            "{hours:02}:{minutes:02}:{seconds:02}{separator}{frames:02}"
+        )
+    }
+}
+
+/// see [SMPTETimecode] for more information
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SMPTETimecodeError {
+    /// The timecode does not match `HH:MM:SS:FF`
+    InvalidFormat,
+    /// A component exceeds its range, e.g. frame 25 at 25 fps
+    OutOfRange,
+    /// The frame number is skipped by drop-frame timecode
+    DroppedFrame,
+}
+
+impl Display for SMPTETimecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidFormat => f.write_str("Timecode does not match HH:MM:SS:FF"),
+            Self::OutOfRange => f.write_str("Timecode component is out of range"),
+            Self::DroppedFrame => f.write_str("Frame number does not exist in drop-frame timecode"),
+        }
+    }
+}
+
+impl Error for SMPTETimecodeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DF: SMPTEFrameRate = SMPTEFrameRate::Fps29_97Drop;
+
+    #[test]
+    fn test_ndi_time_arithmetic() {
+        let time = NDITime::from_ffi(1_000);
+        let ms = Duration::from_millis(1);
+        assert_eq!((time + ms).to_ffi(), 11_000);
+        assert_eq!((time - ms).to_ffi(), -9_000);
+        assert_eq!((time + ms) - time, Some(NDITimeDelta::from_ticks(10_000)));
+        assert_eq!(
+            (time - ms - time).unwrap().abs_duration(),
+            Duration::from_millis(1)
+        );
+        assert_eq!((time - (time + ms)).unwrap().to_duration(), None);
+
+        let mut assigned = time;
+        assigned += Duration::from_nanos(150);
+        assert_eq!(assigned.to_ffi(), 1_001);
+        assigned -= Duration::from_nanos(100);
+        assert_eq!(assigned, time);
+
+        assert_eq!(NDITime::MAX.checked_add(Duration::from_nanos(100)), None);
+        assert_eq!(NDITime::MAX.saturating_add(ms), NDITime::MAX);
+        assert_eq!(NDITime::MIN.checked_sub(Duration::from_nanos(100)), None);
+        assert_eq!(NDITime::MIN.saturating_sub(ms), NDITime::MIN);
+        assert_eq!(NDITime::MIN - NDITime::MAX, None);
+
+        let delta = (NDITime::MIN - NDITime::UNIX_EPOCH).unwrap();
+        assert_eq!(delta.checked_neg(), None);
+        assert_eq!(-delta, NDITimeDelta::from_ticks(i64::MAX));
+        assert_eq!(
+            NDITimeDelta::from_ticks(5).checked_neg(),
+            Some(NDITimeDelta::from_ticks(-5))
+        );
+    }
+
+    #[test]
+    fn test_ndi_time_sentinel() {
+        let ms = Duration::from_millis(1);
+        assert_eq!(NDITime::UNDEFINED + ms, NDITime::UNDEFINED);
+        assert_eq!(NDITime::UNDEFINED - ms, NDITime::UNDEFINED);
+        assert_eq!(NDITime::UNDEFINED.saturating_add(ms), NDITime::UNDEFINED);
+        assert_eq!(NDITime::UNDEFINED.checked_add(ms), None);
+        assert_eq!(NDITime::UNDEFINED - NDITime::UNIX_EPOCH, None);
+        assert_eq!(NDITime::UNIX_EPOCH - NDITime::SYNTHESIZE, None);
+        assert_eq!(NDITime::UNDEFINED.to_utc(), None);
+        assert_eq!(
+            NDITime::from_unix_duration(Duration::from_secs(1 << 60)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_system_time() {
+        let time = SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789);
+        let ndi = NDITime::from_system_time(time).unwrap();
+        assert_eq!(ndi.to_ffi(), 17_000_000_001_234_567);
+        assert_eq!(
+            ndi.to_utc(),
+            Some(SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_700))
+        );
+
+        let before = SystemTime::UNIX_EPOCH - Duration::from_secs(1);
+        let ndi = NDITime::try_from(before).unwrap();
+        assert_eq!(ndi.to_ffi(), -TICKS_PER_SECOND);
+        assert_eq!(ndi.to_utc(), Some(before));
+
+        let now = NDITime::now();
+        assert!(now > NDITime::from_system_time(time).unwrap());
+        assert!(!now.is_default());
+    }
+
+    fn tc(s: &str, rate: SMPTEFrameRate) -> SMPTETimecode {
+        SMPTETimecode::parse(s, rate).unwrap()
+    }
+
+    #[test]
+    fn test_drop_frame_boundaries() {
+        assert_eq!((tc("00:00:59;29", DF) + 1).to_string(), "00:01:00;02");
+        assert_eq!((tc("00:01:00;02", DF) - 1).to_string(), "00:00:59;29");
+        assert_eq!((tc("00:09:59;29", DF) + 1).to_string(), "00:10:00;00");
+        assert_eq!((tc("00:10:59;29", DF) + 1).to_string(), "00:11:00;02");
+        assert_eq!((tc("23:59:59;29", DF) + 1).to_string(), "00:00:00;00");
+
+        assert_eq!(tc("00:01:00;02", DF).frame_count(), 1800);
+        assert_eq!(tc("00:10:00;00", DF).frame_count(), 17_982);
+        assert_eq!(
+            SMPTETimecode::new(0, 1, 0, 1, DF),
+            Err(SMPTETimecodeError::DroppedFrame)
+        );
+        assert!(SMPTETimecode::new(0, 10, 0, 0, DF).is_ok());
+        assert!(SMPTETimecode::new(0, 1, 1, 0, DF).is_ok());
+
+        let df60 = SMPTEFrameRate::Fps59_94Drop;
+        assert_eq!((tc("00:00:59;59", df60) + 1).to_string(), "00:01:00;04");
+        assert_eq!(
+            SMPTETimecode::new(0, 1, 0, 3, df60),
+            Err(SMPTETimecodeError::DroppedFrame)
+        );
+
+        // non-drop-frame does not skip anything
+        let ndf = SMPTEFrameRate::Fps29_97;
+        assert_eq!((tc("00:00:59:29", ndf) + 1).to_string(), "00:01:00:00");
+    }
+
+    #[test]
+    fn test_components_round_trip() {
+        for rate in [DF, SMPTEFrameRate::Fps59_94Drop, SMPTEFrameRate::Fps25] {
+            for frame_count in 0..rate.frames_per_day() {
+                let timecode = SMPTETimecode::from_frame_count(frame_count, rate).unwrap();
+                let (h, m, s, f) = timecode.components();
+                assert_eq!(SMPTETimecode::new(h, m, s, f, rate), Ok(timecode));
+            }
+            assert_eq!(
+                SMPTETimecode::from_frame_count(rate.frames_per_day(), rate),
+                None
+            );
+        }
+        assert_eq!(DF.frames_per_day(), 2_589_408);
+    }
+
+    #[test]
+    fn test_ndi_time() {
+        let hour = NDITime::from_ffi(3600 * TICKS_PER_SECOND);
+        let day = NDITime::from_ffi(20_000 * TICKS_PER_DAY + 1234);
+
+        let timecode = SMPTETimecode::from_ndi_time(hour, SMPTEFrameRate::Fps25).unwrap();
+        assert_eq!(timecode.to_string(), "01:00:00:00");
+        // drop-frame matches the wall clock every ten minutes
+        let timecode = SMPTETimecode::from_ndi_time(hour, DF).unwrap();
+        assert_eq!(timecode.to_string(), "01:00:00;00");
+        let timecode =
+            SMPTETimecode::from_ndi_time_rational(hour, Rational32::new_raw(30_000, 1001), false)
+                .unwrap();
+        assert_eq!(timecode.to_string(), "00:59:56:12");
+
+        for rate in [DF, SMPTEFrameRate::Fps59_94, SMPTEFrameRate::Fps60] {
+            for frame_count in [0, 1, 1799, 1800, 17_982, 2_589_000] {
+                let timecode = SMPTETimecode::from_frame_count(frame_count, rate).unwrap();
+                let time = timecode.to_ndi_time(day).unwrap();
+                assert_eq!(time.to_ffi() / TICKS_PER_DAY, 20_000);
+                assert_eq!(SMPTETimecode::from_ndi_time(time, rate), Some(timecode));
+            }
+        }
+
+        assert_eq!(SMPTETimecode::from_ndi_time(NDITime::UNDEFINED, DF), None);
+        assert_eq!(
+            SMPTETimecode::from_ndi_time(NDITime::from_ffi(-1), SMPTEFrameRate::Fps25)
+                .unwrap()
+                .to_string(),
+            "23:59:59:24"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let rate = SMPTEFrameRate::Fps25;
+        assert_eq!(
+            SMPTETimecode::parse("1:00:00:00", rate),
+            Err(SMPTETimecodeError::InvalidFormat)
+        );
+        assert_eq!(
+            SMPTETimecode::parse("01:00-00:00", rate),
+            Err(SMPTETimecodeError::InvalidFormat)
+        );
+        assert_eq!(
+            SMPTETimecode::parse("01:00:00:+1", rate),
+            Err(SMPTETimecodeError::InvalidFormat)
+        );
+        assert_eq!(
+            SMPTETimecode::parse("01:00:00:25", rate),
+            Err(SMPTETimecodeError::OutOfRange)
+        );
+        assert_eq!(
+            SMPTETimecode::parse("24:00:00:00", rate),
+            Err(SMPTETimecodeError::OutOfRange)
+        );
+        assert_eq!(tc("10:20:30.04", rate).to_string(), "10:20:30:04");
+    }
+
+    #[test]
+    fn test_rates() {
+        let ntsc = Rational32::new_raw(30_000, 1001);
+        assert_eq!(SMPTEFrameRate::from_rational(ntsc, true), Some(DF));
+        assert_eq!(
+            SMPTEFrameRate::from_rational(Rational32::new_raw(25, 1), true),
+            None
+        );
+        assert_eq!(
+            SMPTEFrameRate::from_rational(Rational32::new_raw(48, 1), false),
+            None
+        );
+        assert_eq!(DF.to_rational(), ntsc);
+        assert_eq!(SMPTEFrameRate::Fps23_976.nominal_fps(), 24);
+
+        // senders do not necessarily reduce their rates
+        let unreduced = [
+            ((30_000, 1000), SMPTEFrameRate::Fps30),
+            ((60_000, 1000), SMPTEFrameRate::Fps60),
+            ((25_000, 1000), SMPTEFrameRate::Fps25),
+            ((60_000, 2002), SMPTEFrameRate::Fps29_97),
+        ];
+        for ((numer, denom), rate) in unreduced {
+            let frame_rate = Rational32::new_raw(numer, denom);
+            assert_eq!(SMPTEFrameRate::from_rational(frame_rate, false), Some(rate));
+        }
+        assert_eq!(
+            SMPTEFrameRate::from_rational(Rational32::new_raw(60_000, 2002), true),
+            Some(DF)
+        );
+        assert_eq!(
+            SMPTEFrameRate::from_rational(Rational32::new_raw(25, 0), false),
+            None
+        );
+
+        let hour = NDITime::from_ffi(3600 * TICKS_PER_SECOND);
+        let timecode =
+            SMPTETimecode::from_ndi_time_rational(hour, Rational32::new_raw(25_000, 1000), false)
+                .unwrap();
+        assert_eq!(timecode.to_string(), "01:00:00:00");
+    }
+
+    #[test]
+    fn test_to_ndi_time_overflow() {
+        let last = SMPTETimecode::parse("23:59:59:24", SMPTEFrameRate::Fps25).unwrap();
+        assert_eq!(last.to_ndi_time(NDITime::MAX), None);
+        assert_eq!(last.to_ndi_time(NDITime::MIN), None);
+
+        let first = SMPTETimecode::from_frame_count(0, SMPTEFrameRate::Fps25).unwrap();
+        let time = first.to_ndi_time(NDITime::MAX).unwrap();
+        assert!(time <= NDITime::MAX && !time.is_default());
+    }
 }
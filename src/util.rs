@@ -49,3 +49,68 @@ impl Error for SourceNameError {
         }
     }
 }
+
+/// Validates a list of NDI groups and joins them into the comma-separated list expected by the SDK.
+///
+/// Group names are trimmed, they must not be empty or contain commas or null characters.
+pub fn validate_groups<I, S>(groups: I) -> Result<CString, GroupNameError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut list = String::new();
+    for group in groups {
+        let group = group.as_ref().trim();
+        if group.is_empty() {
+            Err(GroupNameError::Empty)?;
+        }
+        if group.contains(',') {
+            Err(GroupNameError::ContainsComma(group.to_owned()))?;
+        }
+        if !list.is_empty() {
+            list.push(',');
+        }
+        list.push_str(group);
+    }
+
+    if list.is_empty() {
+        Err(GroupNameError::Empty)?;
+    }
+
+    CString::new(list).map_err(GroupNameError::NulError)
+}
+
+/// see [validate_groups] for more information
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroupNameError {
+    /// The input contained Nul characters
+    NulError(std::ffi::NulError),
+    /// A group name or the whole list is empty
+    Empty,
+    /// A group name contains a comma, which is used as separator
+    ContainsComma(String),
+}
+
+impl std::fmt::Display for GroupNameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NulError(nul_error) => write!(
+                f,
+                "Group list contains null characters at index {}",
+                nul_error.nul_position()
+            ),
+            Self::Empty => f.write_str("Group names must not be empty"),
+            Self::ContainsComma(group) => write!(f, "Group name {group:?} contains a comma"),
+        }
+    }
+}
+
+impl Error for GroupNameError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::NulError(nul_error) => Some(nul_error),
+            Self::Empty | Self::ContainsComma(_) => None,
+        }
+    }
+}
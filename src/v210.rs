@@ -0,0 +1,464 @@
+//! V210 (10bit 4:2:2) buffers as used by most SDI capture and playout hardware
+//!
+//! NDI does not transport V210 directly, it has to be converted to/from [FourCCVideo::P216].
+//! This is a pure Rust replacement for `NDIlib_util_V210_to_P216` and `NDIlib_util_P216_to_V210`.
+//!
+//! Every line consists of blocks of 6 pixels packed into 4 little endian 32bit words (16 bytes)
+//! with 3 components each (`Cb Y Cr`, `Y Cb Y`, `Cr Y Cb`, `Y Cr Y`).
+//! Lines are padded to a multiple of 48 pixels (128 bytes).
+//!
+//! 10bit samples are stored in the most significant bits of the 16bit P216 samples.
+
+use std::error::Error;
+
+use crate::{
+    buffer_info::PlaneKind,
+    enums::NDIFieldedFrameMode,
+    four_cc::FourCCVideo,
+    frame::video::{VideoFrame, VideoFrameAccessError},
+    resolution::Resolution,
+};
+
+/// Number of 10bit components stored in a 32bit word
+const COMPONENTS_PER_WORD: usize = 3;
+
+/// A V210 frame buffer
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct V210Buffer {
+    resolution: Resolution,
+    field_mode: NDIFieldedFrameMode,
+    line_stride: usize,
+    data: Vec<u8>,
+}
+
+impl V210Buffer {
+    /// The minimum line stride in bytes for the given width (width is padded to a multiple of 48 pixels)
+    pub const fn min_line_stride(width: usize) -> usize {
+        width.div_ceil(48) * 128
+    }
+
+    /// Allocates a new (black) buffer with the minimum line stride
+    pub fn new(resolution: Resolution, field_mode: NDIFieldedFrameMode) -> Self {
+        let line_stride = Self::min_line_stride(resolution.x);
+        let mut buffer = V210Buffer {
+            resolution,
+            field_mode,
+            line_stride,
+            data: vec![0; line_stride * lines(resolution, field_mode)],
+        };
+        buffer.fill_black();
+        buffer
+    }
+
+    /// Wraps existing V210 data, e.g. from a capture card.
+    ///
+    /// The line stride must be at least [V210Buffer::min_line_stride] and a multiple of 4 bytes.
+    pub fn from_data(
+        resolution: Resolution,
+        field_mode: NDIFieldedFrameMode,
+        line_stride: usize,
+        data: Vec<u8>,
+    ) -> Result<Self, V210Error> {
+        let min_line_stride = Self::min_line_stride(resolution.x);
+        if line_stride < min_line_stride || !line_stride.is_multiple_of(4) {
+            Err(V210Error::InvalidLineStride {
+                line_stride,
+                min_line_stride,
+            })?;
+        }
+
+        let expected = line_stride * lines(resolution, field_mode);
+        if data.len() != expected {
+            Err(V210Error::InvalidSize {
+                expected,
+                actual: data.len(),
+            })?;
+        }
+
+        Ok(V210Buffer {
+            resolution,
+            field_mode,
+            line_stride,
+            data,
+        })
+    }
+
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    pub fn field_mode(&self) -> NDIFieldedFrameMode {
+        self.field_mode
+    }
+
+    /// The stride/size of a single line in bytes.
+    pub fn line_stride(&self) -> usize {
+        self.line_stride
+    }
+
+    /// The number of lines stored in the buffer. Takes into account the field mode.
+    pub fn lines(&self) -> usize {
+        self.data.len() / self.line_stride
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+
+    pub fn into_inner(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Gets a single line
+    pub fn row(&self, y: usize) -> Option<&[u8]> {
+        self.data
+            .get(y * self.line_stride..(y + 1) * self.line_stride)
+    }
+
+    fn fill_black(&mut self) {
+        let width = self.resolution.x;
+        let black = |i: usize| if i.is_multiple_of(2) { 512 } else { 64 };
+        for line in self.data.chunks_exact_mut(self.line_stride) {
+            pack_line(line, (0..width * 2).map(black));
+        }
+    }
+
+    /// Unpacks the buffer into a newly allocated P216 frame.
+    ///
+    /// Only the layout related properties (resolution and field mode) are set on the frame.
+    pub fn to_p216(&self) -> VideoFrame {
+        let mut frame = VideoFrame::new();
+        frame
+            .set_resolution(self.resolution)
+            .expect("[Invariant Error] Fresh frame is already allocated");
+        frame
+            .set_four_cc(FourCCVideo::P216)
+            .expect("[Invariant Error] Fresh frame is already allocated");
+        frame
+            .set_frame_format(self.field_mode)
+            .expect("[Invariant Error] Fresh frame is already allocated");
+        frame.alloc();
+
+        let width = self.resolution.x;
+        let mut planes = frame
+            .planes_mut()
+            .expect("[Invariant Error] Freshly allocated frame is not writable");
+        let mut luma_plane = planes.take(PlaneKind::Y).unwrap();
+        let mut chroma_plane = planes.take(PlaneKind::UV).unwrap();
+        let mut luma_plane = luma_plane.samples16_mut().unwrap();
+        let mut chroma_plane = chroma_plane.samples16_mut().unwrap();
+
+        for ((line, mut luma), mut chroma) in self
+            .data
+            .chunks_exact(self.line_stride)
+            .zip(luma_plane.rows_mut())
+            .zip(chroma_plane.rows_mut())
+        {
+            // components are stored in UYVY order
+            let components: Vec<u16> = unpack_line(line).take(width * 2).collect();
+            chroma.copy_from(components.iter().step_by(2).map(|c| c << 6));
+            luma.copy_from(components.iter().skip(1).step_by(2).map(|c| c << 6));
+        }
+
+        frame
+    }
+
+    /// Packs a P216 frame into a V210 buffer with the minimum line stride.
+    ///
+    /// The 6 least significant bits of every sample are rounded off.
+    pub fn from_p216(frame: &VideoFrame) -> Result<Self, V210Error> {
+        let format = frame.four_cc();
+        if format != Some(FourCCVideo::P216) {
+            Err(V210Error::NotP216(format))?;
+        }
+
+        let planes = frame.planes().map_err(V210Error::FrameAccess)?;
+        let info = planes.buffer_info();
+        let (luma_plane, chroma_plane) = (
+            planes.y().and_then(|p| p.samples16()).unwrap(),
+            planes.uv().and_then(|p| p.samples16()).unwrap(),
+        );
+
+        let mut buffer = V210Buffer::new(info.resolution, info.field_mode);
+        let width = info.resolution.x;
+
+        for ((line, luma), chroma) in buffer
+            .data
+            .chunks_exact_mut(buffer.line_stride)
+            .zip(luma_plane.rows())
+            .zip(chroma_plane.rows())
+        {
+            let to_10bit = |sample: u16| ((sample as u32 + 32) >> 6).min(1023) as u16;
+            // UYVY order
+            let components = (0..width * 2).map(|i| {
+                let sample = if i.is_multiple_of(2) { &chroma } else { &luma }.get(i / 2);
+                to_10bit(sample.unwrap())
+            });
+            pack_line(line, components);
+        }
+
+        Ok(buffer)
+    }
+}
+
+impl std::fmt::Debug for V210Buffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("V210Buffer")
+            .field("resolution", &self.resolution)
+            .field("field_mode", &self.field_mode)
+            .field("line_stride", &self.line_stride)
+            .field("size", &self.data.len())
+            .finish()
+    }
+}
+
+impl TryFrom<&VideoFrame> for V210Buffer {
+    type Error = V210Error;
+
+    fn try_from(frame: &VideoFrame) -> Result<Self, Self::Error> {
+        V210Buffer::from_p216(frame)
+    }
+}
+
+fn lines(resolution: Resolution, field_mode: NDIFieldedFrameMode) -> usize {
+    if field_mode.is_single_field() {
+        resolution.y / 2
+    } else {
+        resolution.y
+    }
+}
+
+/// Yields all 10bit components of a line in memory order (including padding)
+fn unpack_line(line: &[u8]) -> impl Iterator<Item = u16> + '_ {
+    line.chunks_exact(4).flat_map(|word| {
+        let word = u32::from_le_bytes([word[0], word[1], word[2], word[3]]);
+        [
+            (word & 0x3ff) as u16,
+            ((word >> 10) & 0x3ff) as u16,
+            ((word >> 20) & 0x3ff) as u16,
+        ]
+    })
+}
+
+/// Packs 10bit components into a line, the remaining space is zeroed
+fn pack_line(line: &mut [u8], components: impl Iterator<Item = u16>) {
+    let mut components = components.fuse();
+    for word in line.chunks_exact_mut(4) {
+        let mut packed = 0u32;
+        for i in 0..COMPONENTS_PER_WORD {
+            let component = components.next().unwrap_or(0) as u32 & 0x3ff;
+            packed |= component << (i * 10);
+        }
+        word.copy_from_slice(&packed.to_le_bytes());
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum V210Error {
+    /// Only P216 frames can be converted to V210
+    NotP216(Option<FourCCVideo>),
+    /// The frame data could not be accessed
+    FrameAccess(VideoFrameAccessError),
+    /// The line stride is too small or not a multiple of 4
+    InvalidLineStride {
+        line_stride: usize,
+        min_line_stride: usize,
+    },
+    /// The buffer size does not match the resolution and line stride
+    InvalidSize { expected: usize, actual: usize },
+}
+
+impl std::fmt::Display for V210Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotP216(Some(format)) => write!(f, "Expected a P216 frame, got {format:?}"),
+            Self::NotP216(None) => f.write_str("Expected a P216 frame, got an unknown format"),
+            Self::FrameAccess(err) => write!(f, "Frame is not readable: {err}"),
+            Self::InvalidLineStride {
+                line_stride,
+                min_line_stride,
+            } => write!(
+                f,
+                "Invalid V210 line stride {line_stride}, needs to be a multiple of 4 and at least {min_line_stride}"
+            ),
+            Self::InvalidSize { expected, actual } => {
+                write!(f, "Invalid V210 buffer size {actual}, expected {expected}")
+            }
+        }
+    }
+}
+
+impl Error for V210Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::FrameAccess(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p216_frame(width: usize, height: usize, sample: impl Fn(usize, usize) -> u16) -> VideoFrame {
+        let mut frame = VideoFrame::new();
+        frame
+            .set_resolution(Resolution::new(width, height))
+            .unwrap();
+        frame.set_four_cc(FourCCVideo::P216).unwrap();
+        frame.alloc();
+        let mut planes = frame.planes_mut().unwrap();
+        for kind in [PlaneKind::Y, PlaneKind::UV] {
+            let mut plane = planes.take(kind).unwrap();
+            let mut plane = plane.samples16_mut().unwrap();
+            for y in 0..height {
+                for x in 0..width {
+                    plane.set(
+                        x,
+                        y,
+                        sample(x, y + if kind == PlaneKind::UV { 1000 } else { 0 }),
+                    );
+                }
+            }
+        }
+        frame
+    }
+
+    #[test]
+    fn test_line_stride() {
+        assert_eq!(V210Buffer::min_line_stride(720), 1920);
+        assert_eq!(V210Buffer::min_line_stride(1280), 3456);
+        assert_eq!(V210Buffer::min_line_stride(1920), 5120);
+        assert_eq!(V210Buffer::min_line_stride(3840), 10240);
+    }
+
+    #[test]
+    fn test_packing() {
+        // Cb Y Cr Y ... = 1, 2, 3, ..., 12
+        let frame = p216_frame(6, 2, |x, y| {
+            let component = if y >= 1000 { x * 2 + 1 } else { x * 2 + 2 };
+            (component as u16) << 6
+        });
+        let v210 = V210Buffer::from_p216(&frame).unwrap();
+        assert_eq!(v210.line_stride(), 128);
+        assert_eq!(v210.lines(), 2);
+
+        let word = |i: usize| {
+            u32::from_le_bytes(v210.row(1).unwrap()[i * 4..i * 4 + 4].try_into().unwrap())
+        };
+        assert_eq!(word(0), 1 | 2 << 10 | 3 << 20);
+        assert_eq!(word(1), 4 | 5 << 10 | 6 << 20);
+        assert_eq!(word(2), 7 | 8 << 10 | 9 << 20);
+        assert_eq!(word(3), 10 | 11 << 10 | 12 << 20);
+        assert_eq!(word(4), 0);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for width in [6, 8, 48, 50, 1920] {
+            let frame = p216_frame(width, 3, |x, y| (((x * 31 + y * 7) % 1024) as u16) << 6);
+            let v210 = V210Buffer::try_from(&frame).unwrap();
+            let back = v210.to_p216();
+            assert_eq!(back.resolution(), frame.resolution());
+            assert_eq!(
+                back.video_data().unwrap().0,
+                frame.video_data().unwrap().0,
+                "width {width}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_rounding() {
+        let frame = p216_frame(6, 2, |_, _| 0xffff);
+        let v210 = V210Buffer::from_p216(&frame).unwrap();
+        let p216 = v210.to_p216();
+        let planes = p216.planes().unwrap();
+        assert_eq!(
+            planes.y().unwrap().samples16().unwrap().get(0, 0),
+            Some(1023 << 6)
+        );
+
+        let frame = p216_frame(6, 2, |_, _| (100 << 6) + 40);
+        let p216 = V210Buffer::from_p216(&frame).unwrap().to_p216();
+        let planes = p216.planes().unwrap();
+        assert_eq!(
+            planes.uv().unwrap().samples16().unwrap().get(3, 1),
+            Some(101 << 6)
+        );
+    }
+
+    #[test]
+    fn test_black() {
+        let v210 = V210Buffer::new(Resolution::new(6, 2), NDIFieldedFrameMode::Progressive);
+        let components: Vec<u16> = unpack_line(v210.row(0).unwrap()).take(12).collect();
+        assert_eq!(
+            components,
+            [512, 64, 512, 64, 512, 64, 512, 64, 512, 64, 512, 64]
+        );
+    }
+
+    #[test]
+    fn test_fields() {
+        let frame = {
+            let mut frame = VideoFrame::new();
+            frame.set_resolution(Resolution::new(8, 4)).unwrap();
+            frame.set_four_cc(FourCCVideo::P216).unwrap();
+            frame.set_frame_format(NDIFieldedFrameMode::Field0).unwrap();
+            frame.alloc();
+            frame
+        };
+        let v210 = V210Buffer::from_p216(&frame).unwrap();
+        assert_eq!(v210.lines(), 2);
+        assert_eq!(v210.to_p216().field_mode(), NDIFieldedFrameMode::Field0);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut uyvy = VideoFrame::new();
+        uyvy.set_resolution(Resolution::new(8, 4)).unwrap();
+        assert_eq!(
+            V210Buffer::from_p216(&uyvy).unwrap_err(),
+            V210Error::NotP216(Some(FourCCVideo::UYVY))
+        );
+
+        let mut p216 = VideoFrame::new();
+        p216.set_resolution(Resolution::new(8, 4)).unwrap();
+        p216.set_four_cc(FourCCVideo::P216).unwrap();
+        assert_eq!(
+            V210Buffer::from_p216(&p216).unwrap_err(),
+            V210Error::FrameAccess(VideoFrameAccessError::NotAllocated)
+        );
+
+        let res = Resolution::new(8, 4);
+        let progressive = NDIFieldedFrameMode::Progressive;
+        assert_eq!(
+            V210Buffer::from_data(res, progressive, 64, vec![0; 256]).unwrap_err(),
+            V210Error::InvalidLineStride {
+                line_stride: 64,
+                min_line_stride: 128
+            }
+        );
+        assert_eq!(
+            V210Buffer::from_data(res, progressive, 130, vec![0; 520]).unwrap_err(),
+            V210Error::InvalidLineStride {
+                line_stride: 130,
+                min_line_stride: 128
+            }
+        );
+        assert_eq!(
+            V210Buffer::from_data(res, progressive, 128, vec![0; 500]).unwrap_err(),
+            V210Error::InvalidSize {
+                expected: 512,
+                actual: 500
+            }
+        );
+        assert!(V210Buffer::from_data(res, progressive, 256, vec![0; 1024]).is_ok());
+    }
+}
@@ -0,0 +1,322 @@
+//! Standard video formats
+//!
+//! A [VideoFormat] bundles everything needed to describe a video signal: the [Resolution], the frame
+//! rate and the [NDIFieldedFrameMode]. Common broadcast formats are available as constants and by
+//! name, e.g. `"1080p59.94"`.
+
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+use num::Rational32;
+
+use crate::{
+    enums::NDIFieldedFrameMode,
+    frame::video::{AlreadyAllocatedError, VideoFrame},
+    resolution::Resolution,
+};
+
+/// A video format, see module docs
+///
+/// The name of interlaced formats uses the field rate (`1080i50` has 25 frames per second), the
+/// `frame_rate` is always the number of full frames per second as used by [VideoFrame::frame_rate].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VideoFormat {
+    pub resolution: Resolution,
+    pub frame_rate: Rational32,
+    pub field_mode: NDIFieldedFrameMode,
+}
+
+const fn progressive(x: usize, y: usize, numer: i32, denom: i32) -> VideoFormat {
+    VideoFormat {
+        resolution: Resolution::new_const(x, y),
+        frame_rate: Rational32::new_raw(numer, denom),
+        field_mode: NDIFieldedFrameMode::Progressive,
+    }
+}
+
+const fn interlaced(x: usize, y: usize, numer: i32, denom: i32) -> VideoFormat {
+    VideoFormat {
+        resolution: Resolution::new_const(x, y),
+        frame_rate: Rational32::new_raw(numer, denom),
+        field_mode: NDIFieldedFrameMode::Interleaved,
+    }
+}
+
+impl VideoFormat {
+    pub const SD480I59_94: Self = interlaced(720, 480, 30_000, 1001);
+    pub const SD576I50: Self = interlaced(720, 576, 25, 1);
+
+    pub const HD720P25: Self = progressive(1280, 720, 25, 1);
+    pub const HD720P29_97: Self = progressive(1280, 720, 30_000, 1001);
+    pub const HD720P30: Self = progressive(1280, 720, 30, 1);
+    pub const HD720P50: Self = progressive(1280, 720, 50, 1);
+    pub const HD720P59_94: Self = progressive(1280, 720, 60_000, 1001);
+    pub const HD720P60: Self = progressive(1280, 720, 60, 1);
+
+    pub const HD1080I50: Self = interlaced(1920, 1080, 25, 1);
+    pub const HD1080I59_94: Self = interlaced(1920, 1080, 30_000, 1001);
+    pub const HD1080I60: Self = interlaced(1920, 1080, 30, 1);
+
+    pub const HD1080P23_976: Self = progressive(1920, 1080, 24_000, 1001);
+    pub const HD1080P24: Self = progressive(1920, 1080, 24, 1);
+    pub const HD1080P25: Self = progressive(1920, 1080, 25, 1);
+    pub const HD1080P29_97: Self = progressive(1920, 1080, 30_000, 1001);
+    pub const HD1080P30: Self = progressive(1920, 1080, 30, 1);
+    pub const HD1080P50: Self = progressive(1920, 1080, 50, 1);
+    pub const HD1080P59_94: Self = progressive(1920, 1080, 60_000, 1001);
+    pub const HD1080P60: Self = progressive(1920, 1080, 60, 1);
+
+    pub const UHD2160P23_976: Self = progressive(3840, 2160, 24_000, 1001);
+    pub const UHD2160P24: Self = progressive(3840, 2160, 24, 1);
+    pub const UHD2160P25: Self = progressive(3840, 2160, 25, 1);
+    pub const UHD2160P29_97: Self = progressive(3840, 2160, 30_000, 1001);
+    pub const UHD2160P30: Self = progressive(3840, 2160, 30, 1);
+    pub const UHD2160P50: Self = progressive(3840, 2160, 50, 1);
+    pub const UHD2160P59_94: Self = progressive(3840, 2160, 60_000, 1001);
+    pub const UHD2160P60: Self = progressive(3840, 2160, 60, 1);
+
+    /// All predefined formats
+    pub const ALL: &'static [VideoFormat] = &[
+        Self::SD480I59_94,
+        Self::SD576I50,
+        Self::HD720P25,
+        Self::HD720P29_97,
+        Self::HD720P30,
+        Self::HD720P50,
+        Self::HD720P59_94,
+        Self::HD720P60,
+        Self::HD1080I50,
+        Self::HD1080I59_94,
+        Self::HD1080I60,
+        Self::HD1080P23_976,
+        Self::HD1080P24,
+        Self::HD1080P25,
+        Self::HD1080P29_97,
+        Self::HD1080P30,
+        Self::HD1080P50,
+        Self::HD1080P59_94,
+        Self::HD1080P60,
+        Self::UHD2160P23_976,
+        Self::UHD2160P24,
+        Self::UHD2160P25,
+        Self::UHD2160P29_97,
+        Self::UHD2160P30,
+        Self::UHD2160P50,
+        Self::UHD2160P59_94,
+        Self::UHD2160P60,
+    ];
+
+    pub fn new(
+        resolution: Resolution,
+        frame_rate: Rational32,
+        field_mode: NDIFieldedFrameMode,
+    ) -> Self {
+        Self {
+            resolution,
+            frame_rate,
+            field_mode,
+        }
+    }
+
+    /// Finds the predefined format matching the given properties
+    pub fn find(
+        resolution: Resolution,
+        frame_rate: Rational32,
+        field_mode: NDIFieldedFrameMode,
+    ) -> Option<Self> {
+        if *frame_rate.denom() == 0 {
+            return None;
+        }
+
+        Self::ALL.iter().copied().find(|format| {
+            format.resolution == resolution
+                && format.frame_rate == frame_rate
+                && format.field_mode == field_mode
+        })
+    }
+
+    /// Detects the format of a (received) frame, `None` if it is not a predefined format
+    pub fn detect(frame: &VideoFrame) -> Option<Self> {
+        Self::find(frame.resolution(), frame.frame_rate(), frame.field_mode())
+    }
+
+    /// Sets resolution, frame rate and field mode of a frame
+    ///
+    /// This will fail if the frame is already allocated.
+    pub fn apply_to(&self, frame: &mut VideoFrame) -> Result<(), AlreadyAllocatedError> {
+        frame.set_resolution(self.resolution)?;
+        frame.set_frame_format(self.field_mode)?;
+        frame.set_frame_rate(self.frame_rate);
+        Ok(())
+    }
+
+    /// The rate used in the name, fields per second for fielded formats
+    ///
+    /// An invalid frame rate with a zero denominator is treated as `0`.
+    fn name_rate(&self) -> f64 {
+        if *self.frame_rate.denom() == 0 {
+            return 0.0;
+        }
+        let frame_rate = *self.frame_rate.numer() as f64 / *self.frame_rate.denom() as f64;
+        if self.field_mode.is_fielded() {
+            frame_rate * 2.0
+        } else {
+            frame_rate
+        }
+    }
+}
+
+impl Display for VideoFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let scan = if self.field_mode.is_fielded() {
+            'i'
+        } else {
+            'p'
+        };
+        let rate = format!("{:.3}", self.name_rate());
+        let rate = rate.trim_end_matches('0').trim_end_matches('.');
+        write!(f, "{}{scan}{rate}", self.resolution.y)
+    }
+}
+
+impl Debug for VideoFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "VideoFormat({self}, {})", self.resolution)
+    }
+}
+
+impl FromStr for VideoFormat {
+    type Err = VideoFormatParseError;
+
+    /// Parses the name of a predefined format, like `1080p59.94` or `1080i50`
+    ///
+    /// NTSC rates may also be rounded to two decimals (`23.98`).
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let scan_pos = name
+            .find(['p', 'P', 'i', 'I'])
+            .ok_or(VideoFormatParseError::InvalidFormat)?;
+        let (height, rest) = name.split_at(scan_pos);
+        let interlaced = rest.starts_with(['i', 'I']);
+
+        if height.is_empty() || !height.bytes().all(|b| b.is_ascii_digit()) {
+            Err(VideoFormatParseError::InvalidFormat)?;
+        }
+        let height: usize = height
+            .parse()
+            .map_err(|_| VideoFormatParseError::InvalidFormat)?;
+
+        let rate = &rest[1..];
+        if rate.is_empty() || !rate.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+            Err(VideoFormatParseError::InvalidFormat)?;
+        }
+        let rate: f64 = rate
+            .parse()
+            .map_err(|_| VideoFormatParseError::InvalidFormat)?;
+
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|format| {
+                format.resolution.y == height
+                    && format.field_mode.is_fielded() == interlaced
+                    && (format.name_rate() - rate).abs() < 0.005
+            })
+            .ok_or(VideoFormatParseError::Unknown)
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoFormatParseError {
+    /// The name does not follow the `<height><p|i><rate>` pattern
+    InvalidFormat,
+    /// There is no predefined format with this name
+    Unknown,
+}
+
+impl Display for VideoFormatParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidFormat => {
+                f.write_str("Video format name does not match <height><p|i><rate>")
+            }
+            Self::Unknown => f.write_str("Unknown video format"),
+        }
+    }
+}
+
+impl Error for VideoFormatParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::four_cc::FourCCVideo;
+
+    #[test]
+    fn test_names() {
+        assert_eq!(VideoFormat::HD1080P59_94.to_string(), "1080p59.94");
+        assert_eq!(VideoFormat::HD1080I59_94.to_string(), "1080i59.94");
+        assert_eq!(VideoFormat::HD1080P23_976.to_string(), "1080p23.976");
+        assert_eq!(VideoFormat::UHD2160P25.to_string(), "2160p25");
+        assert_eq!(VideoFormat::SD576I50.to_string(), "576i50");
+
+        for format in VideoFormat::ALL {
+            assert_eq!(format.to_string().parse(), Ok(*format));
+        }
+
+        assert_eq!("720P50".parse(), Ok(VideoFormat::HD720P50));
+        assert_eq!("2160p23.98".parse(), Ok(VideoFormat::UHD2160P23_976));
+        assert_eq!(
+            VideoFormat::HD1080I50.frame_rate,
+            Rational32::new_raw(25, 1)
+        );
+
+        let invalid = VideoFormat::new(
+            Resolution::new(1920, 1080),
+            Rational32::new_raw(30, 0),
+            NDIFieldedFrameMode::Progressive,
+        );
+        assert_eq!(invalid.to_string(), "1080p0");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for name in ["", "1080", "p50", "1080p", "1080x50", "1080p-50", "1080p5o"] {
+            assert_eq!(
+                name.parse::<VideoFormat>(),
+                Err(VideoFormatParseError::InvalidFormat),
+                "{name}"
+            );
+        }
+        assert_eq!(
+            "1080p48".parse::<VideoFormat>(),
+            Err(VideoFormatParseError::Unknown)
+        );
+        assert_eq!(
+            "720i50".parse::<VideoFormat>(),
+            Err(VideoFormatParseError::Unknown)
+        );
+    }
+
+    #[test]
+    fn test_frame() {
+        let mut frame = VideoFrame::new();
+        VideoFormat::HD1080I59_94.apply_to(&mut frame).unwrap();
+        assert_eq!(frame.resolution(), Resolution::new(1920, 1080));
+        assert_eq!(frame.field_mode(), NDIFieldedFrameMode::Interleaved);
+        assert_eq!(VideoFormat::detect(&frame), Some(VideoFormat::HD1080I59_94));
+
+        // unreduced rates are detected as well
+        frame.set_frame_rate(Rational32::new_raw(60_000, 2002));
+        assert_eq!(VideoFormat::detect(&frame), Some(VideoFormat::HD1080I59_94));
+        frame.set_frame_rate(Rational32::new_raw(48, 1));
+        assert_eq!(VideoFormat::detect(&frame), None);
+
+        frame.set_four_cc(FourCCVideo::UYVY).unwrap();
+        frame.alloc();
+        assert!(VideoFormat::HD720P50.apply_to(&mut frame).is_err());
+    }
+}